@@ -15,6 +15,17 @@ pub const WEECHAT_RC_OK: c_int = 0;
 pub const WEECHAT_RC_OK_EAT: c_int = 1;
 pub const WEECHAT_RC_ERROR: c_int = -1;
 
+/* return codes for the process hook callback */
+pub const WEECHAT_HOOK_PROCESS_RUNNING: c_int = -1;
+pub const WEECHAT_HOOK_PROCESS_ERROR: c_int = -2;
+pub const WEECHAT_HOOK_PROCESS_CHILD: c_int = -3;
+
+/* flags for string_split function */
+pub const WEECHAT_STRING_SPLIT_STRIP_LEFT: c_int = 1 << 0;
+pub const WEECHAT_STRING_SPLIT_STRIP_RIGHT: c_int = 1 << 1;
+pub const WEECHAT_STRING_SPLIT_COLLAPSE_SEPS: c_int = 1 << 2;
+pub const WEECHAT_STRING_SPLIT_KEEP_EOL: c_int = 1 << 3;
+
 pub const WEECHAT_CONFIG_OPTION_SET_OK_CHANGED: c_int = 2;
 pub const WEECHAT_CONFIG_OPTION_SET_OK_SAME_VALUE: c_int = 1;
 pub const WEECHAT_CONFIG_OPTION_SET_ERROR: c_int = 0;
@@ -23,3 +34,7 @@ pub const WEECHAT_CONFIG_OPTION_SET_OPTION_NOT_FOUND: c_int = -1;
 pub const WEECHAT_CONFIG_READ_OK: c_int = 0;
 pub const WEECHAT_CONFIG_READ_MEMORY_ERROR: c_int = -1;
 pub const WEECHAT_CONFIG_READ_FILE_NOT_FOUND: c_int = -2;
+
+pub const WEECHAT_CONFIG_WRITE_OK: c_int = 0;
+pub const WEECHAT_CONFIG_WRITE_ERROR: c_int = -1;
+pub const WEECHAT_CONFIG_WRITE_MEMORY_ERROR: c_int = -2;
@@ -40,8 +40,8 @@ use weechat::{
     buffer::Buffer,
     config,
     hooks::{
-        Command, CommandCallback, CommandRun, CommandRunCallback, CommandSettings,
-        ModifierCallback, ModifierData, ModifierHook,
+        Command, CommandCallback, CommandGuard, CommandRun, CommandRunCallback, CommandRunSettings,
+        CommandSettings, ModifierCallback, ModifierData, ModifierHook,
     },
     infolist::InfolistVariable,
     plugin, Args, Plugin, Prefix, ReturnCode, Weechat,
@@ -166,8 +166,7 @@ struct InputState {
 impl InputState {
     /// Restore the input state on the given buffer.
     fn restore_for_buffer(&self, buffer: &Buffer) {
-        buffer.set_input(&self.input_string);
-        buffer.set_input_position(self.input_position);
+        buffer.set_input_with_position(&self.input_string, self.input_position);
     }
 }
 
@@ -384,26 +383,22 @@ impl std::fmt::Display for BufferList {
                     "".to_string()
                 };
 
-                let buffer_name: String = buffer_data
-                    .short_name
-                    .chars()
-                    .enumerate()
-                    .map(|(i, g)| {
-                        // We don't highlight the buffer number and it isn't yet
-                        // part of the string, so move our current index for the
-                        // buffer name length.
-                        let i = i + buffer_number.len();
-
-                        let color = if buffer_data.indices.contains(&i) {
-                            &name_highlight
-                        } else {
-                            &name_color
-                        };
-
-                        format!("{}{}{}", color, g, Weechat::color("reset"))
-                    })
+                // We don't highlight the buffer number and it isn't yet part
+                // of the string, so move the indices by the buffer number
+                // length.
+                let indices: Vec<usize> = buffer_data
+                    .indices
+                    .iter()
+                    .filter_map(|i| i.checked_sub(buffer_number.len()))
                     .collect();
 
+                let buffer_name = Weechat::colorize_indices(
+                    &buffer_data.short_name,
+                    &indices,
+                    &name_highlight,
+                    &name_color,
+                );
+
                 let buffer_number = format!(
                     "{}{}{}",
                     number_color,
@@ -423,29 +418,21 @@ impl std::fmt::Display for BufferList {
 struct Hooks {
     modifier: ModifierHook,
     input_command: CommandRun,
-    buffer_command: CommandRun,
-    window_command: CommandRun,
+    command_guard: CommandGuard,
 }
 
 impl Hooks {
     fn new(inner_go: &InnerGo) -> Self {
         // Override our input command.
-        let input_command = CommandRun::new("2000|/input *", inner_go.clone())
-            .expect("Can't override input command");
-
-        // Disable buffer commands while in go mode.
-        let buffer_command =
-            CommandRun::new("2000|/buffer *", |_: &Weechat, _: &Buffer, _: Cow<str>| {
-                ReturnCode::OkEat
-            })
-            .expect("Can't override buffer command");
+        let input_command = CommandRun::with_settings(
+            CommandRunSettings::new("/input *").priority(2000),
+            inner_go.clone(),
+        )
+        .expect("Can't override input command");
 
-        // Disable window commands while in go mode.
-        let window_command =
-            CommandRun::new("2000|/window *", |_: &Weechat, _: &Buffer, _: Cow<str>| {
-                ReturnCode::OkEat
-            })
-            .expect("Can't override window command");
+        // Disable buffer and window commands while in go mode.
+        let command_guard = CommandGuard::new(&["/buffer *", "/window *"], 2000)
+            .expect("Can't override buffer and window commands");
 
         // Override our buffer input text so we can display the go buffer line.
         let modifier = ModifierHook::new("input_text_display_with_cursor", inner_go.clone())
@@ -453,8 +440,7 @@ impl Hooks {
 
         Hooks {
             input_command,
-            buffer_command,
-            window_command,
+            command_guard,
             modifier,
         }
     }
@@ -21,7 +21,7 @@ use weechat::{infolist::InfolistVariable, Args, Plugin, Weechat};
 
 use weechat::{
     buffer::{Buffer, BufferCloseCallback, BufferInputCallback},
-    config::{BooleanOptionSettings, Config, ConfigOption, ConfigSectionSettings},
+    config::{Config, ConfigBuilder, ConfigOption},
     hooks::{Command, CommandCallback, CommandSettings},
     plugin, Prefix,
 };
@@ -142,7 +142,8 @@ impl RipgrepCommand {
 
     /// Guess the log file from the buffer name.
     fn file_from_name(&self, full_name: &str) -> PathBuf {
-        let weechat_home = Weechat::info_get("weechat_dir", "").expect("Can't find Weechat home");
+        let weechat_home =
+            Weechat::info_get_cached("weechat_dir").expect("Can't find Weechat home");
         let mut file = Path::new(&weechat_home).join("logs");
         let mut full_name = full_name.to_owned();
         full_name.push_str(".weechatlog");
@@ -285,22 +286,15 @@ impl CommandCallback for RipgrepCommand {
 
 impl Plugin for Ripgrep {
     fn init(_: &Weechat, _args: Args) -> Result<Self, ()> {
-        let mut config = Config::new("ripgrep")?;
-
-        {
-            let section_settings = ConfigSectionSettings::new("main");
-            let mut section = config
-                .new_section(section_settings)
-                .expect("Can't create main config section");
-
-            let option_settings = BooleanOptionSettings::new("go_to_buffer")
-                .description("Automatically go to grep buffer when search is over.")
-                .default_value(true);
-
-            section
-                .new_boolean_option(option_settings)
-                .expect("Can't create boolean option");
-        }
+        let config = ConfigBuilder::new("ripgrep")
+            .section("main", |section| {
+                section.boolean(
+                    "go_to_buffer",
+                    true,
+                    "Automatically go to grep buffer when search is over.",
+                );
+            })
+            .build()?;
 
         let config = Rc::new(RefCell::new(config));
 
@@ -22,7 +22,7 @@ use itertools::sorted;
 use weechat::{
     buffer::{Buffer, BufferBuilder, BufferCloseCallback, BufferHandle, BufferInputCallback},
     hooks::{Command, CommandCallback, CommandSettings},
-    infolist::InfolistVariable,
+    infolist::{InfolistFieldType, InfolistVariable},
     plugin, Args, Plugin, Prefix, Weechat,
 };
 
@@ -45,11 +45,7 @@ impl InnerInfolist {
         let infolist_names: Vec<String> = infolist
             .filter_map(|item| {
                 let name = item.get("infolist_name")?;
-                if let InfolistVariable::String(n) = name {
-                    Some(n.to_string())
-                } else {
-                    None
-                }
+                name.as_string().map(|n| n.to_string())
             })
             .collect();
 
@@ -161,6 +157,35 @@ impl InnerInfolist {
 
                     prefix = "".to_string();
                 }
+
+                // Pointers that don't point to a buffer and binary buffers
+                // can't be fetched, display only their names.
+                for (name, field_type) in sorted(item.fields()) {
+                    let value_type = match field_type {
+                        InfolistFieldType::Pointer => "ptr",
+                        InfolistFieldType::Buffer => "buf",
+                        _ => continue,
+                    };
+
+                    if item.get(&name).is_some() {
+                        continue;
+                    }
+
+                    buffer.print_date_tags(
+                        0,
+                        &["no_filter"],
+                        &format!(
+                            "{}{:.<30} {}{}{}",
+                            prefix,
+                            name,
+                            Weechat::color("brown"),
+                            value_type,
+                            Weechat::color("chat"),
+                        ),
+                    );
+
+                    prefix = "".to_string();
+                }
             }
         }
     }
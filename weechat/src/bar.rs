@@ -0,0 +1,383 @@
+//! Weechat bars.
+//!
+//! Bars are areas around the chat area that display bar items, e.g. the
+//! status bar or the nicklist.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use weechat::bar::{BarPosition, BarSettings, BarType};
+//!
+//! # let weechat = unsafe { weechat::Weechat::weechat() };
+//! let settings = BarSettings::new("my_plugin_status")
+//!     .bar_type(BarType::Window)
+//!     .position(BarPosition::Top)
+//!     .size(1)
+//!     .items(&["buffer_name", "my_plugin_item"]);
+//!
+//! let bar = weechat.bar_new(settings).expect("Can't create bar");
+//! ```
+
+use std::marker::PhantomData;
+
+use weechat_sys::{t_gui_bar, t_weechat_plugin};
+
+use crate::{LossyCString, Weechat};
+
+/// The type of a bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarType {
+    /// The bar is displayed once, outside of the windows.
+    Root,
+    /// The bar is displayed in every window.
+    Window,
+}
+
+impl BarType {
+    fn as_str(&self) -> &str {
+        match self {
+            BarType::Root => "root",
+            BarType::Window => "window",
+        }
+    }
+}
+
+/// The position of a bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarPosition {
+    /// The bar is displayed above the chat area.
+    Top,
+    /// The bar is displayed below the chat area.
+    Bottom,
+    /// The bar is displayed left of the chat area.
+    Left,
+    /// The bar is displayed right of the chat area.
+    Right,
+}
+
+impl BarPosition {
+    fn as_str(&self) -> &str {
+        match self {
+            BarPosition::Top => "top",
+            BarPosition::Bottom => "bottom",
+            BarPosition::Left => "left",
+            BarPosition::Right => "right",
+        }
+    }
+}
+
+/// The way items are placed in a bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarFilling {
+    /// Items are placed next to each other.
+    Horizontal,
+    /// Items are placed below each other.
+    Vertical,
+    /// Items are placed in columns, the text is filled from left to right.
+    ColumnsHorizontal,
+    /// Items are placed in columns, the text is filled from top to bottom.
+    ColumnsVertical,
+}
+
+impl BarFilling {
+    fn as_str(&self) -> &str {
+        match self {
+            BarFilling::Horizontal => "horizontal",
+            BarFilling::Vertical => "vertical",
+            BarFilling::ColumnsHorizontal => "columns_horizontal",
+            BarFilling::ColumnsVertical => "columns_vertical",
+        }
+    }
+}
+
+/// Settings for a new bar.
+pub struct BarSettings {
+    name: String,
+    hidden: bool,
+    priority: i32,
+    bar_type: BarType,
+    condition: String,
+    position: BarPosition,
+    filling_top_bottom: BarFilling,
+    filling_left_right: BarFilling,
+    size: u32,
+    size_max: u32,
+    color_fg: String,
+    color_delim: String,
+    color_bg: String,
+    color_bg_inactive: String,
+    separator: bool,
+    items: String,
+}
+
+impl BarSettings {
+    /// Create new settings that can be used to create a new bar.
+    ///
+    /// By default the bar is a visible window bar at the bottom of the
+    /// windows, its size is computed from its content and it doesn't contain
+    /// any items.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the new bar.
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        BarSettings {
+            name: name.into(),
+            hidden: false,
+            priority: 0,
+            bar_type: BarType::Window,
+            condition: String::new(),
+            position: BarPosition::Bottom,
+            filling_top_bottom: BarFilling::Horizontal,
+            filling_left_right: BarFilling::Vertical,
+            size: 0,
+            size_max: 0,
+            color_fg: "default".to_owned(),
+            color_delim: "default".to_owned(),
+            color_bg: "default".to_owned(),
+            color_bg_inactive: "default".to_owned(),
+            separator: false,
+            items: String::new(),
+        }
+    }
+
+    /// Should the bar be hidden.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Set the priority of the bar, bars with a higher priority are displayed
+    /// closer to the edge of the screen.
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Set the type of the bar.
+    pub fn bar_type(mut self, bar_type: BarType) -> Self {
+        self.bar_type = bar_type;
+        self
+    }
+
+    /// Set the condition that decides if the bar is displayed in a window.
+    ///
+    /// This is only used for window bars. The condition can be `active`,
+    /// `inactive`, `nicklist` or an expression that is evaluated using
+    /// `/eval`, e.g. `${nicklist} && ${window.win_width} > 100`.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition of the bar.
+    pub fn condition<C: Into<String>>(mut self, condition: C) -> Self {
+        self.condition = condition.into();
+        self
+    }
+
+    /// Set the position of the bar.
+    pub fn position(mut self, position: BarPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Set the filling of the bar when it's positioned at the top or bottom.
+    pub fn filling_top_bottom(mut self, filling: BarFilling) -> Self {
+        self.filling_top_bottom = filling;
+        self
+    }
+
+    /// Set the filling of the bar when it's positioned left or right.
+    pub fn filling_left_right(mut self, filling: BarFilling) -> Self {
+        self.filling_left_right = filling;
+        self
+    }
+
+    /// Set the size of the bar, 0 means that the size is computed from the
+    /// content of the bar.
+    pub fn size(mut self, size: u32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the maximum size of the bar, 0 means that there is no limit.
+    pub fn size_max(mut self, size_max: u32) -> Self {
+        self.size_max = size_max;
+        self
+    }
+
+    /// Set the color of the text.
+    pub fn color_fg<C: Into<String>>(mut self, color: C) -> Self {
+        self.color_fg = color.into();
+        self
+    }
+
+    /// Set the color of the delimiters.
+    pub fn color_delim<C: Into<String>>(mut self, color: C) -> Self {
+        self.color_delim = color.into();
+        self
+    }
+
+    /// Set the color of the background.
+    pub fn color_bg<C: Into<String>>(mut self, color: C) -> Self {
+        self.color_bg = color.into();
+        self
+    }
+
+    /// Set the color of the background when the window of the bar isn't
+    /// active.
+    pub fn color_bg_inactive<C: Into<String>>(mut self, color: C) -> Self {
+        self.color_bg_inactive = color.into();
+        self
+    }
+
+    /// Should a separator line be displayed between the bar and other bars or
+    /// the chat area.
+    pub fn separator(mut self, separator: bool) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Set the items of the bar.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The names of the bar items that should be displayed in the
+    ///     bar. Items can be combined using `+`, e.g. `buffer_number+buffer_name`,
+    ///     in which case they are displayed without a space between them.
+    pub fn items(mut self, items: &[&str]) -> Self {
+        self.items = items.join(",");
+        self
+    }
+}
+
+/// A Weechat bar.
+///
+/// Bars are saved in the Weechat config, they aren't removed when the object
+/// is dropped.
+pub struct Bar<'a> {
+    weechat: *mut t_weechat_plugin,
+    ptr: *mut t_gui_bar,
+    phantom: PhantomData<&'a Weechat>,
+}
+
+impl Weechat {
+    /// Create a new bar.
+    ///
+    /// Returns an error if a bar with the same name already exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - Settings that decide how the bar should be created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn bar_new(&self, settings: BarSettings) -> Result<Bar, ()> {
+        Weechat::check_thread();
+
+        let bar_new = self.get().bar_new.unwrap();
+
+        let bool_str = |value: bool| if value { "on" } else { "off" };
+
+        let name = LossyCString::new(settings.name);
+        let hidden = LossyCString::new(bool_str(settings.hidden));
+        let priority = LossyCString::new(settings.priority.to_string());
+        let bar_type = LossyCString::new(settings.bar_type.as_str());
+        let condition = LossyCString::new(settings.condition);
+        let position = LossyCString::new(settings.position.as_str());
+        let filling_top_bottom = LossyCString::new(settings.filling_top_bottom.as_str());
+        let filling_left_right = LossyCString::new(settings.filling_left_right.as_str());
+        let size = LossyCString::new(settings.size.to_string());
+        let size_max = LossyCString::new(settings.size_max.to_string());
+        let color_fg = LossyCString::new(settings.color_fg);
+        let color_delim = LossyCString::new(settings.color_delim);
+        let color_bg = LossyCString::new(settings.color_bg);
+        let color_bg_inactive = LossyCString::new(settings.color_bg_inactive);
+        let separator = LossyCString::new(bool_str(settings.separator));
+        let items = LossyCString::new(settings.items);
+
+        let bar_ptr = unsafe {
+            bar_new(
+                name.as_ptr(),
+                hidden.as_ptr(),
+                priority.as_ptr(),
+                bar_type.as_ptr(),
+                condition.as_ptr(),
+                position.as_ptr(),
+                filling_top_bottom.as_ptr(),
+                filling_left_right.as_ptr(),
+                size.as_ptr(),
+                size_max.as_ptr(),
+                color_fg.as_ptr(),
+                color_delim.as_ptr(),
+                color_bg.as_ptr(),
+                color_bg_inactive.as_ptr(),
+                separator.as_ptr(),
+                items.as_ptr(),
+            )
+        };
+
+        if bar_ptr.is_null() {
+            Err(())
+        } else {
+            Ok(self.bar_from_ptr(bar_ptr))
+        }
+    }
+
+    /// Search for a bar by its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the bar.
+    pub fn bar_search(&self, name: &str) -> Option<Bar> {
+        let bar_search = self.get().bar_search.unwrap();
+        let name = LossyCString::new(name);
+
+        let bar_ptr = unsafe { bar_search(name.as_ptr()) };
+
+        if bar_ptr.is_null() {
+            None
+        } else {
+            Some(self.bar_from_ptr(bar_ptr))
+        }
+    }
+
+    fn bar_from_ptr(&self, bar_ptr: *mut t_gui_bar) -> Bar {
+        Bar {
+            weechat: self.ptr,
+            ptr: bar_ptr,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a> Bar<'a> {
+    /// Set a property of the bar.
+    ///
+    /// Returns true if the property was set, false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `property` - The name of the property, e.g. `hidden`, `position`,
+    ///     `size` or `items`, the same names as the options of the bar in the
+    ///     `weechat.bar` config section are used.
+    ///
+    /// * `value` - The new value of the property.
+    pub fn set(&self, property: &str, value: &str) -> bool {
+        let weechat = Weechat::from_ptr(self.weechat);
+        let bar_set = weechat.get().bar_set.unwrap();
+
+        let property = LossyCString::new(property);
+        let value = LossyCString::new(value);
+
+        unsafe { bar_set(self.ptr, property.as_ptr(), value.as_ptr()) == 1 }
+    }
+
+    /// Remove the bar.
+    pub fn remove(self) {
+        let weechat = Weechat::from_ptr(self.weechat);
+        let bar_remove = weechat.get().bar_remove.unwrap();
+
+        unsafe { bar_remove(self.ptr) };
+    }
+}
@@ -1,7 +1,7 @@
 use std::{borrow::Cow, collections::HashMap, ffi::c_void, marker::PhantomData};
 
 use crate::{buffer::Buffer, Weechat};
-use weechat_sys::{t_hdata, t_weechat_plugin};
+use weechat_sys::{t_gui_buffer, t_hdata, t_weechat_plugin};
 
 /// An iterator that steps over the lines of the buffer.
 pub struct BufferLines<'a> {
@@ -34,10 +34,12 @@ impl<'a> Iterator for BufferLines<'a> {
                 self.done = true;
             }
 
+            let line_pointer = self.first_line;
             self.first_line = unsafe { weechat.hdata_move(line_hdata, self.first_line, 1) };
 
             Some(BufferLine {
                 weechat,
+                line_pointer,
                 line_data_pointer,
                 buffer: PhantomData,
             })
@@ -65,10 +67,12 @@ impl<'a> DoubleEndedIterator for BufferLines<'a> {
                 self.done = true;
             }
 
+            let line_pointer = self.last_line;
             self.last_line = unsafe { weechat.hdata_move(line_hdata, self.last_line, -1) };
 
             Some(BufferLine {
                 weechat,
+                line_pointer,
                 line_data_pointer,
                 buffer: PhantomData,
             })
@@ -89,17 +93,64 @@ pub struct LineData<'a> {
 
 /// The buffer line, makes it possible to modify the printed message and other
 /// line data.
+///
+/// Weechat refreshes the buffer automatically when a line is modified, callers
+/// don't need to trigger a redraw after using one of the setters.
 pub struct BufferLine<'a> {
     weechat: Weechat,
+    line_pointer: *mut c_void,
     line_data_pointer: *mut c_void,
     buffer: PhantomData<&'a Buffer<'a>>,
 }
 
 impl<'a> BufferLine<'a> {
+    /// Create a buffer line from a pointer to a Weechat `t_gui_line` struct.
+    ///
+    /// Returns None if the line doesn't contain any line data.
+    pub(crate) fn from_line_ptr(weechat: Weechat, line_pointer: *mut c_void) -> Option<Self> {
+        let line_data_pointer = unsafe {
+            let line_hdata = weechat.hdata_get("line");
+            weechat.hdata_pointer(line_hdata, line_pointer, "data")
+        };
+
+        if line_data_pointer.is_null() {
+            None
+        } else {
+            Some(BufferLine {
+                weechat,
+                line_pointer,
+                line_data_pointer,
+                buffer: PhantomData,
+            })
+        }
+    }
+
     fn hdata(&self) -> *mut t_hdata {
         unsafe { self.weechat.hdata_get("line_data") }
     }
 
+    /// Get the raw pointer of the `t_gui_line` struct this line represents.
+    pub(crate) fn line_ptr(&self) -> *mut c_void {
+        self.line_pointer
+    }
+
+    /// Get the raw pointer of the buffer this line belongs to.
+    pub(crate) fn buffer_ptr(&self) -> *mut t_gui_buffer {
+        unsafe {
+            self.weechat
+                .hdata_pointer(self.hdata(), self.line_data_pointer, "buffer")
+                as *mut t_gui_buffer
+        }
+    }
+
+    /// Get the line number of a line in a buffer with free content.
+    pub(crate) fn y(&self) -> i32 {
+        unsafe {
+            self.weechat
+                .hdata_integer(self.hdata(), self.line_data_pointer, "y")
+        }
+    }
+
     fn update_line(&self, hashmap: HashMap<&str, &str>) {
         unsafe {
             self.weechat
@@ -137,9 +188,25 @@ impl<'a> BufferLine<'a> {
 
     /// Set the message to the given new value.
     ///
+    /// This can be used to implement message edits.
+    ///
     /// # Arguments
     ///
     /// * `new_value` - The new message that should be set on the line.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use weechat::buffer::BufferBuilder;
+    /// # let buffer_handle = BufferBuilder::new("test")
+    /// #    .build()
+    /// #    .unwrap();
+    /// # let buffer = buffer_handle.upgrade().unwrap();
+    /// buffer.print("Hello wrold");
+    ///
+    /// let line = buffer.lines().next_back().unwrap();
+    /// // The buffer shows the fixed message, no redraw is needed.
+    /// line.set_message("Hello world");
+    /// ```
     pub fn set_message(&self, new_value: &str) {
         let mut hashmap = HashMap::new();
 
@@ -196,28 +263,54 @@ impl<'a> BufferLine<'a> {
         }
     }
 
-    /// Get the list of tags of the line.
-    pub fn tags(&self) -> Vec<Cow<str>> {
+    /// Is the line displayed.
+    ///
+    /// Lines that are hidden by a filter (`/filter`) aren't displayed.
+    pub fn displayed(&self) -> bool {
         unsafe {
-            let count = self.weechat.hdata_var_array_size(
+            self.weechat
+                .hdata_char(self.hdata(), self.line_data_pointer, "displayed")
+                != 0
+        }
+    }
+
+    fn tags_iter(&self) -> impl Iterator<Item = Cow<str>> {
+        let count = unsafe {
+            self.weechat
+                .hdata_var_array_size(self.hdata(), self.line_data_pointer, "tags_array")
+        };
+
+        (0..count).map(move |i| unsafe {
+            self.weechat.hdata_string(
                 self.hdata(),
                 self.line_data_pointer,
-                "tags_array",
-            );
-
-            let mut tags = Vec::with_capacity(count as usize);
-
-            for i in 0..count {
-                let tag = self.weechat.hdata_string(
-                    self.hdata(),
-                    self.line_data_pointer,
-                    &format!("{}|tags_array", i),
-                );
-                tags.push(tag);
-            }
+                &format!("{}|tags_array", i),
+            )
+        })
+    }
 
-            tags
-        }
+    /// Get the list of tags of the line.
+    pub fn tags(&self) -> Vec<Cow<str>> {
+        self.tags_iter().collect()
+    }
+
+    /// Check if the line has the given tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag that should be checked for.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags_iter().any(|t| t == tag)
+    }
+
+    /// Get the tags of the line that start with the given prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix the tags should start with, e.g. `nick_` to
+    ///     find the tag containing the nick of the line author.
+    pub fn tags_matching(&self, prefix: &str) -> Vec<Cow<str>> {
+        self.tags_iter().filter(|t| t.starts_with(prefix)).collect()
     }
 
     /// Set the tags of the line to the new value.
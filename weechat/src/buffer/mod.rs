@@ -3,14 +3,17 @@
 mod lines;
 mod nick;
 mod nickgroup;
+mod nicklist;
 mod window;
 
 use std::{
     borrow::Cow,
     cmp::{Ord, Ordering},
+    collections::HashMap,
     ffi::{c_void, CStr},
     marker::PhantomData,
     ptr,
+    time::SystemTime,
 };
 
 use std::{cell::Cell, rc::Rc};
@@ -20,7 +23,7 @@ use async_trait::async_trait;
 #[cfg(feature = "async")]
 use futures::future::LocalBoxFuture;
 
-use crate::{LossyCString, Weechat};
+use crate::{infolist::Infolist, LossyCString, Weechat};
 use libc::{c_char, c_int};
 use weechat_sys::{
     t_gui_buffer, t_gui_nick, t_hdata, t_weechat_plugin, WEECHAT_RC_ERROR, WEECHAT_RC_OK,
@@ -30,9 +33,121 @@ pub use crate::buffer::{
     lines::{BufferLine, BufferLines, LineData},
     nick::{Nick, NickSettings},
     nickgroup::NickGroup,
+    nicklist::{NickGroupIterator, NickIterator},
     window::Window,
 };
 
+/// The type of a buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferType {
+    /// A buffer with formatted content, lines are appended to the buffer.
+    Formatted,
+    /// A buffer with free content, every line can be displayed and
+    /// overwritten individually using `Buffer::print_y()`.
+    Free,
+}
+
+impl Default for BufferType {
+    fn default() -> Self {
+        BufferType::Formatted
+    }
+}
+
+/// An opaque identifier of a buffer.
+///
+/// The identifier can be stored across callbacks to remember buffers that
+/// weren't created by the plugin and thus have no `BufferHandle`, use
+/// `Weechat::buffer_by_id()` to get the buffer back.
+///
+/// The identifier wraps the pointer of the buffer. Weechat may reuse the
+/// memory of a closed buffer for a new one, an identifier of a closed buffer
+/// might thus point to a different buffer later on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BufferId(*mut t_gui_buffer);
+
+/// The notify level of a buffer, controls which messages add the buffer to
+/// the hotlist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyLevel {
+    /// The buffer is never added to the hotlist.
+    None,
+    /// Only highlights add the buffer to the hotlist.
+    Highlight,
+    /// Messages from users and highlights add the buffer to the hotlist.
+    Message,
+    /// All messages add the buffer to the hotlist.
+    All,
+}
+
+impl NotifyLevel {
+    pub(crate) fn value(&self) -> i32 {
+        match self {
+            NotifyLevel::None => 0,
+            NotifyLevel::Highlight => 1,
+            NotifyLevel::Message => 2,
+            NotifyLevel::All => 3,
+        }
+    }
+
+    pub(crate) fn from_value(value: i32) -> Self {
+        match value {
+            0 => NotifyLevel::None,
+            1 => NotifyLevel::Highlight,
+            2 => NotifyLevel::Message,
+            _ => NotifyLevel::All,
+        }
+    }
+}
+
+/// The priority a buffer is added to the hotlist with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotlistPriority {
+    /// Low priority, e.g. join or part messages.
+    Low,
+    /// A message from a user.
+    Message,
+    /// A private message.
+    Private,
+    /// A highlight.
+    Highlight,
+}
+
+impl HotlistPriority {
+    pub(crate) fn value(&self) -> &str {
+        match self {
+            HotlistPriority::Low => "0",
+            HotlistPriority::Message => "1",
+            HotlistPriority::Private => "2",
+            HotlistPriority::Highlight => "3",
+        }
+    }
+}
+
+/// The notification a printed line triggers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notify {
+    /// The line doesn't add the buffer to the hotlist.
+    None,
+    /// The line is treated as a message from a user.
+    Message,
+    /// The line is treated as a private message.
+    Private,
+    /// The line is treated as a highlight, regardless of the highlight words
+    /// of the user.
+    Highlight,
+}
+
+impl Notify {
+    pub(crate) fn tag(&self) -> &str {
+        match self {
+            Notify::None => "notify_none",
+            Notify::Message => "notify_message",
+            Notify::Private => "notify_private",
+            Notify::Highlight => "notify_highlight",
+        }
+    }
+}
+
 /// A Weechat buffer.
 ///
 /// A buffer contains the data displayed on the screen.
@@ -149,6 +264,23 @@ impl BufferHandle {
             Ok(buffer)
         }
     }
+
+    /// Is the buffer the handle points to still valid.
+    ///
+    /// This is a cheap check that doesn't upgrade the handle, returns false
+    /// once Weechat closed the buffer.
+    pub fn is_valid(&self) -> bool {
+        !self.buffer_ptr.get().is_null()
+    }
+
+    /// Get the full name of the buffer the handle points to.
+    ///
+    /// This is the full name the buffer had when the handle was created, it
+    /// is available even after the buffer was closed, but it may be stale if
+    /// the buffer was renamed in the meantime.
+    pub fn full_name(&self) -> &str {
+        &self.buffer_name
+    }
 }
 
 #[cfg(feature = "async")]
@@ -208,6 +340,9 @@ impl<T: FnMut(&Weechat, &Buffer, Cow<str>) -> Result<(), ()> + 'static> BufferIn
 pub trait BufferCloseCallback {
     /// Callback that will be called before the buffer is closed.
     ///
+    /// The buffer is closed after the callback returns, the return value
+    /// can't prevent the buffer from being closed.
+    ///
     /// # Arguments
     ///
     /// * `weechat` - A Weechat context.
@@ -260,6 +395,7 @@ impl<T: FnMut(BufferHandle, String) -> LocalBoxFuture<'static, ()> + 'static>
 /// Builder for the creation of a buffer.
 pub struct BufferBuilderAsync {
     pub(crate) name: String,
+    pub(crate) buffer_type: BufferType,
     pub(crate) input_callback: Option<Box<dyn BufferInputCallbackAsync>>,
     pub(crate) close_callback: Option<Box<dyn BufferCloseCallback>>,
 }
@@ -267,6 +403,7 @@ pub struct BufferBuilderAsync {
 /// Builder for the creation of a buffer.
 pub struct BufferBuilder {
     pub(crate) name: String,
+    pub(crate) buffer_type: BufferType,
     pub(crate) input_callback: Option<Box<dyn BufferInputCallback>>,
     pub(crate) close_callback: Option<Box<dyn BufferCloseCallback>>,
 }
@@ -317,6 +454,7 @@ impl BufferBuilderAsync {
     pub fn new(name: &str) -> Self {
         BufferBuilderAsync {
             name: name.to_owned(),
+            buffer_type: BufferType::default(),
             input_callback: None,
             close_callback: None,
         }
@@ -344,6 +482,18 @@ impl BufferBuilderAsync {
         self
     }
 
+    /// Set the type of the buffer.
+    ///
+    /// Buffers are created as buffers with formatted content by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer_type` - The type the buffer should have.
+    pub fn buffer_type(mut self, buffer_type: BufferType) -> Self {
+        self.buffer_type = buffer_type;
+        self
+    }
+
     /// Build the configured buffer.
     pub fn build(self) -> Result<BufferHandle, ()> {
         Weechat::buffer_new_with_async(self)
@@ -393,6 +543,7 @@ impl BufferBuilder {
     pub fn new(name: &str) -> Self {
         BufferBuilder {
             name: name.to_owned(),
+            buffer_type: BufferType::default(),
             input_callback: None,
             close_callback: None,
         }
@@ -419,6 +570,18 @@ impl BufferBuilder {
         self
     }
 
+    /// Set the type of the buffer.
+    ///
+    /// Buffers are created as buffers with formatted content by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer_type` - The type the buffer should have.
+    pub fn buffer_type(mut self, buffer_type: BufferType) -> Self {
+        self.buffer_type = buffer_type;
+        self
+    }
+
     /// Build the configured buffer.
     pub fn build(self) -> Result<BufferHandle, ()> {
         Weechat::buffer_new(self)
@@ -464,15 +627,68 @@ impl Weechat {
         }
     }
 
+    /// Get the buffer with the given identifier.
+    ///
+    /// The identifier is validated against the list of open buffers before
+    /// it's turned into a buffer, this walks the list of buffers, use
+    /// `BufferHandle`s for buffers the plugin created instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the buffer, as returned by `Buffer::id()`.
+    ///
+    /// Returns `None` if no buffer with the given identifier is open.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// # let weechat = unsafe { Weechat::weechat() };
+    /// let id = weechat.current_buffer().id();
+    ///
+    /// // Later on, possibly in a different callback.
+    /// if let Some(buffer) = weechat.buffer_by_id(id) {
+    ///     buffer.print("Still here");
+    /// }
+    /// ```
+    pub fn buffer_by_id(&self, id: BufferId) -> Option<Buffer> {
+        if self.buffer_ptr_is_valid(id.0) {
+            Some(self.buffer_from_ptr(id.0))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn buffer_ptr_is_valid(&self, buffer_ptr: *mut t_gui_buffer) -> bool {
+        unsafe {
+            let hdata = self.hdata_get("buffer");
+            let list = self.hdata_get_list(hdata, "gui_buffers");
+            self.hdata_check_pointer(hdata, list, buffer_ptr as *mut c_void)
+        }
+    }
+
     /// Get the currently open buffer
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no current buffer, this can happen if Weechat
+    /// hasn't created its core buffer yet, e.g. very early during startup.
+    /// Use `try_current_buffer()` to handle this case.
     pub fn current_buffer(&self) -> Buffer {
+        self.try_current_buffer().expect("No open buffer found")
+    }
+
+    /// Get the currently open buffer.
+    ///
+    /// Returns `None` if there is no current buffer.
+    pub fn try_current_buffer(&self) -> Option<Buffer> {
         let buffer_search = self.get().buffer_search.unwrap();
 
         let buf_ptr = unsafe { buffer_search(ptr::null(), ptr::null()) };
         if buf_ptr.is_null() {
-            panic!("No open buffer found");
+            None
         } else {
-            self.buffer_from_ptr(buf_ptr)
+            Some(self.buffer_from_ptr(buf_ptr))
         }
     }
 
@@ -489,6 +705,88 @@ impl Weechat {
         }
     }
 
+    /// Run the given command in the main/core buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command that should run.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let weechat = unsafe { weechat::Weechat::weechat() };
+    /// weechat.run_command_on_core("/window splith").unwrap();
+    /// ```
+    pub fn run_command_on_core(&self, command: &str) -> Result<(), ()> {
+        self.core_buffer().run_command(command)
+    }
+
+    /// Display a message on the buffer with the given full name.
+    ///
+    /// This is a shorthand for searching the buffer using `buffer_search()`
+    /// and printing to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer_full_name` - The full name of the buffer, e.g.
+    ///     `irc.libera.#weechat`.
+    ///
+    /// * `message` - The message that will be displayed.
+    ///
+    /// Returns an empty error and prints nothing if no buffer with the given
+    /// name exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// if Weechat::print_to("irc.libera.#weechat", "Hello").is_err() {
+    ///     Weechat::print("The channel buffer is gone");
+    /// }
+    /// ```
+    pub fn print_to(buffer_full_name: &str, message: &str) -> Result<(), ()> {
+        Weechat::print_to_date_tags(buffer_full_name, 0, &[], message)
+    }
+
+    /// Display a message with attached date and tags on the buffer with the
+    /// given full name.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer_full_name` - The full name of the buffer.
+    ///
+    /// * `date` - A unix time-stamp representing the date of the message, 0
+    ///     means now.
+    ///
+    /// * `tags` - A list of tags that will be applied to the printed line.
+    ///
+    /// * `message` - The message that will be displayed.
+    ///
+    /// Returns an empty error and prints nothing if no buffer with the given
+    /// name exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn print_to_date_tags(
+        buffer_full_name: &str,
+        date: i64,
+        tags: &[&str],
+        message: &str,
+    ) -> Result<(), ()> {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let buffer = weechat.buffer_search("==", buffer_full_name).ok_or(())?;
+        buffer.print_date_tags(date, tags, message);
+
+        Ok(())
+    }
+
     #[cfg(feature = "async")]
     #[cfg_attr(feature = "docs", doc(cfg(r#async)))]
     fn buffer_new_with_async(builder: BufferBuilderAsync) -> Result<BufferHandle, ()> {
@@ -604,6 +902,10 @@ impl Weechat {
         let buffer = weechat.buffer_from_ptr(buf_ptr);
         let buffer_cell = Rc::new(Cell::new(buf_ptr));
 
+        if builder.buffer_type == BufferType::Free {
+            buffer.set_free_content();
+        }
+
         pointers.buffer_cell = Some(buffer_cell.clone());
 
         Ok(BufferHandle {
@@ -719,6 +1021,10 @@ impl Weechat {
         let buffer = weechat.buffer_from_ptr(buf_ptr);
         let buffer_cell = Rc::new(Cell::new(buf_ptr));
 
+        if builder.buffer_type == BufferType::Free {
+            buffer.set_free_content();
+        }
+
         pointers.buffer_cell = Some(buffer_cell.clone());
 
         Ok(BufferHandle {
@@ -764,6 +1070,11 @@ impl Buffer<'_> {
         self.inner.is_closing()
     }
 
+    /// Get an identifier of the buffer that can be stored across callbacks.
+    pub fn id(&self) -> BufferId {
+        BufferId(self.ptr())
+    }
+
     fn mark_as_closing(&self) {
         self.inner.mark_as_closing()
     }
@@ -817,6 +1128,149 @@ impl Buffer<'_> {
         }
     }
 
+    /// Display a message on the buffer with the given time and tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - The time of the message, `SystemTime::UNIX_EPOCH` means
+    ///     now. Times before the unix epoch are clamped to the epoch, so they
+    ///     are displayed using the current time as well.
+    ///
+    /// * `tags` - A list of tags that will be applied to the printed line.
+    ///
+    /// * `message` - The message that will be displayed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::time::{Duration, SystemTime};
+    /// # use weechat::buffer::BufferBuilder;
+    /// # let buffer_handle = BufferBuilder::new("test").build().unwrap();
+    /// # let buffer = buffer_handle.upgrade().unwrap();
+    /// let an_hour_ago = SystemTime::now() - Duration::from_secs(60 * 60);
+    /// buffer.print_at(an_hour_ago, &["no_highlight"], "This happened an hour ago");
+    /// ```
+    pub fn print_at(&self, time: SystemTime, tags: &[&str], message: &str) {
+        let date = time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+
+        self.print_date_tags(date, tags, message)
+    }
+
+    /// Display a message on the buffer that triggers the given notification.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message that will be displayed.
+    ///
+    /// * `notify` - The notification the message should trigger.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use weechat::buffer::{BufferBuilder, Notify};
+    /// # let buffer_handle = BufferBuilder::new("test")
+    /// #    .build()
+    /// #    .unwrap();
+    /// # let buffer = buffer_handle.upgrade().unwrap();
+    /// buffer.print_with_notify("Your build has finished", Notify::Highlight);
+    /// ```
+    pub fn print_with_notify(&self, message: &str, notify: Notify) {
+        self.print_date_tags(0, &[notify.tag()], message)
+    }
+
+    /// Display a message on a specific line of a buffer with free content.
+    ///
+    /// Calling this on a buffer with formatted content has no effect, use
+    /// `set_free_content()` to switch the buffer type first.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The line number, starting from 0, that should be
+    ///     overwritten. A negative value displays the message after the last
+    ///     line of the buffer.
+    ///
+    /// * `message` - The message that will be displayed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use weechat::buffer::BufferBuilder;
+    /// # let buffer_handle = BufferBuilder::new("test")
+    /// #    .build()
+    /// #    .unwrap();
+    /// # let buffer = buffer_handle.upgrade().unwrap();
+    /// buffer.set_free_content();
+    ///
+    /// buffer.print_y(0, "Name     Status");
+    /// buffer.print_y(1, "foo      online");
+    /// ```
+    pub fn print_y(&self, line: i32, message: &str) {
+        self.print_y_date_tags(line, 0, &[], message)
+    }
+
+    /// Display a message on a specific line of a buffer with free content,
+    /// with attached date and tags.
+    ///
+    /// The plugin API this crate binds doesn't provide `printf_y_date_tags()`,
+    /// the message is displayed using `printf_y()` and the date and tags are
+    /// set on the displayed line afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The line number, starting from 0, that should be
+    ///     overwritten. A negative value displays the message after the last
+    ///     line of the buffer.
+    ///
+    /// * `date` - A unix time-stamp representing the date of the message, 0
+    ///     means now.
+    ///
+    /// * `tags` - A list of tags that will be applied to the line.
+    ///
+    /// * `message` - The message that will be displayed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use weechat::buffer::BufferBuilder;
+    /// # let buffer_handle = BufferBuilder::new("test")
+    /// #    .build()
+    /// #    .unwrap();
+    /// # let buffer = buffer_handle.upgrade().unwrap();
+    /// buffer.set_free_content();
+    ///
+    /// buffer.print_y_date_tags(0, 0, &["header"], "Name     Status");
+    /// ```
+    pub fn print_y_date_tags(&self, line: i32, date: i64, tags: &[&str], message: &str) {
+        let weechat = self.weechat();
+        let printf_y = weechat.get().printf_y.unwrap();
+
+        let fmt_str = LossyCString::new("%s");
+        let c_message = LossyCString::new(message);
+
+        unsafe { printf_y(self.ptr(), line, fmt_str.as_ptr(), c_message.as_ptr()) }
+
+        // Weechat ignores printf_y() on formatted buffers, don't touch their
+        // last line.
+        if (date == 0 && tags.is_empty()) || self.buffer_type() != BufferType::Free {
+            return;
+        }
+
+        let printed = if line < 0 {
+            self.lines().next_back()
+        } else {
+            self.lines().find(|l| l.y() == line)
+        };
+
+        if let Some(printed) = printed {
+            if date != 0 {
+                printed.set_date(date);
+            }
+
+            if !tags.is_empty() {
+                printed.set_tags(tags);
+            }
+        }
+    }
+
     /// Search for a nicklist group by name
     ///
     /// # Arguments
@@ -868,6 +1322,33 @@ impl Buffer<'_> {
         }
     }
 
+    /// Get an iterator over all the nicks of the buffer nicklist.
+    ///
+    /// The nicks are returned in the order Weechat displays them, groups
+    /// are traversed depth-first.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use weechat::buffer::BufferBuilder;
+    /// # let buffer_handle = BufferBuilder::new("test")
+    /// #    .build()
+    /// #    .unwrap();
+    /// # let buffer = buffer_handle.upgrade().unwrap();
+    /// let nicks: Vec<String> = buffer.nicks().map(|n| n.name().to_string()).collect();
+    /// ```
+    pub fn nicks(&self) -> NickIterator {
+        NickIterator::new(self.weechat().ptr, self.ptr())
+    }
+
+    /// Get an iterator over all the nick groups of the buffer nicklist.
+    ///
+    /// The groups are returned in the order Weechat displays them, nested
+    /// groups are traversed depth-first. The hidden root group isn't
+    /// returned.
+    pub fn groups(&self) -> NickGroupIterator {
+        NickGroupIterator::new(self.weechat().ptr, self.ptr())
+    }
+
     fn search_nick_helper(
         weechat: &Weechat,
         buffer_ptr: *mut t_gui_buffer,
@@ -1054,6 +1535,26 @@ impl Buffer<'_> {
     }
 
     fn get_string(&self, property: &str) -> Option<Cow<str>> {
+        self.get_property_string(property)
+    }
+
+    fn get_integer(&self, property: &str) -> i32 {
+        self.get_property_integer(property)
+    }
+
+    /// Get the value of a string buffer property.
+    ///
+    /// Commonly used properties are "plugin", "name", "full_name",
+    /// "short_name", "title", "input", "text_search_input" and "highlight_words".
+    /// Local variables can be fetched using a "localvar_" prefix, e.g.
+    /// "localvar_type".
+    ///
+    /// # Arguments
+    ///
+    /// * `property` - The name of the property that should be fetched.
+    ///
+    /// Returns None if the property doesn't exist or has no value.
+    pub fn get_property_string(&self, property: &str) -> Option<Cow<str>> {
         let weechat = self.weechat();
 
         let buffer_get = weechat.get().buffer_get_string.unwrap();
@@ -1069,7 +1570,19 @@ impl Buffer<'_> {
         }
     }
 
-    fn get_integer(&self, property: &str) -> i32 {
+    /// Get the value of an integer buffer property.
+    ///
+    /// Commonly used properties are "number", "layout_number", "type",
+    /// "notify", "num_displayed", "active", "hidden", "zoomed",
+    /// "print_hooks_enabled", "day_change", "lines_hidden", "prefix_max_length",
+    /// "time_for_each_line", "nicklist", "input_pos" and "input_multiline".
+    ///
+    /// # Arguments
+    ///
+    /// * `property` - The name of the property that should be fetched.
+    ///
+    /// Returns 0 if the property doesn't exist, like Weechat does.
+    pub fn get_property_integer(&self, property: &str) -> i32 {
         let weechat = self.weechat();
 
         let buffer_get = weechat.get().buffer_get_integer.unwrap();
@@ -1099,6 +1612,29 @@ impl Buffer<'_> {
         self.set(&format!("localvar_set_{}", property), value)
     }
 
+    /// Remove a buffer localvar.
+    ///
+    /// # Arguments
+    ///
+    /// * `property` - The name of the localvar that should be removed.
+    pub fn remove_localvar(&self, property: &str) {
+        self.set(&format!("localvar_del_{}", property), "")
+    }
+
+    /// Get all the localvars of the buffer.
+    ///
+    /// The localvars are returned in no particular order.
+    pub fn localvars(&self) -> HashMap<String, String> {
+        let weechat = self.weechat();
+        let hdata = self.hdata_pointer();
+
+        unsafe {
+            let localvars =
+                weechat.hdata_hashtable(hdata, self.ptr() as *mut c_void, "local_variables");
+            weechat.hashtable_to_hashmap(localvars)
+        }
+    }
+
     /// Get the full name of the buffer.
     pub fn full_name(&self) -> Cow<str> {
         self.get_string("full_name").unwrap()
@@ -1151,6 +1687,15 @@ impl Buffer<'_> {
         self.set("time_for_each_line", "0");
     }
 
+    /// Display the time for all lines in the buffer.
+    ///
+    /// This is the default for new buffers. The time is formatted using the
+    /// strftime format of the `weechat.look.buffer_time_format` option, the
+    /// format is global and can't be set per buffer.
+    pub fn enable_time_for_each_line(&self) {
+        self.set("time_for_each_line", "1");
+    }
+
     /// Disable the nicklist for this buffer.
     pub fn disable_nicklist(&self) {
         self.set("nicklist", "0")
@@ -1185,6 +1730,67 @@ impl Buffer<'_> {
         self.set("title", title);
     }
 
+    /// Get the type of the buffer.
+    pub fn buffer_type(&self) -> BufferType {
+        match self.get_integer("type") {
+            1 => BufferType::Free,
+            _ => BufferType::Formatted,
+        }
+    }
+
+    /// Switch the buffer to a buffer with free content.
+    ///
+    /// Lines of a buffer with free content can be displayed and overwritten
+    /// individually using `print_y()`.
+    pub fn set_free_content(&self) {
+        self.set("type", "free");
+    }
+
+    /// Get the notify level of the buffer.
+    pub fn notify_level(&self) -> NotifyLevel {
+        NotifyLevel::from_value(self.get_integer("notify"))
+    }
+
+    /// Set the notify level of the buffer.
+    ///
+    /// The notify level controls which messages will add the buffer to the
+    /// hotlist.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The new notify level of the buffer.
+    pub fn set_notify_level(&self, level: NotifyLevel) {
+        self.set("notify", &level.value().to_string());
+    }
+
+    /// Set the unread marker after the last line of the buffer.
+    ///
+    /// There is no way to put the marker after a specific line. The `unread`
+    /// buffer property can only move it after the last line. The marker is
+    /// stored in the `last_read_line` variable of the `lines` hdata, and
+    /// Weechat doesn't allow `hdata_update()` to change that variable.
+    pub fn set_unread(&self) {
+        self.set("unread", "");
+    }
+
+    /// Add the buffer to the hotlist.
+    ///
+    /// If the buffer is already in the hotlist with a lower priority, the
+    /// priority is raised.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - The priority the buffer will be added to the hotlist
+    ///     with.
+    pub fn add_to_hotlist(&self, priority: HotlistPriority) {
+        self.set("hotlist", priority.value());
+    }
+
+    /// Remove the buffer from the hotlist.
+    pub fn remove_from_hotlist(&self) {
+        self.set("hotlist", "-1");
+    }
+
     /// Disable logging for this buffer.
     pub fn disable_log(&self) {
         self.set("localvar_set_no_log", "1");
@@ -1214,16 +1820,108 @@ impl Buffer<'_> {
         }
     }
 
+    /// Close the buffer and report if the buffer was closed.
+    ///
+    /// # Ordering
+    ///
+    /// Closing a buffer happens in the following order:
+    ///
+    /// 1. The close callback of the buffer runs, the buffer is still valid
+    ///    while the callback runs and can be used from the callback.
+    /// 2. `BufferHandle`s of the buffer are invalidated, upgrading them fails
+    ///    from now on.
+    /// 3. Weechat frees the buffer.
+    ///
+    /// Weechat ignores the return value of the close callback, returning an
+    /// error from the callback does not prevent the buffer from being
+    /// closed. Hooks and other state that refer to the buffer should be
+    /// torn down in the close callback at the latest.
+    ///
+    /// Returns an empty error if the buffer was already being closed or if
+    /// Weechat refused to close the buffer, e.g. the core buffer can't be
+    /// closed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// # let weechat = unsafe { Weechat::weechat() };
+    /// let buffer = weechat.core_buffer();
+    /// if buffer.close_with_result().is_err() {
+    ///     Weechat::print("The core buffer can't be closed");
+    /// }
+    /// ```
+    pub fn close_with_result(&self) -> Result<(), ()> {
+        if self.is_closing() {
+            return Err(());
+        }
+
+        let weechat = self.weechat();
+        let buffer_ptr = self.ptr();
+
+        self.close();
+
+        if weechat.buffer_ptr_is_valid(buffer_ptr) {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
     /// Get the contents of the input
+    ///
+    /// Weechat versions that support multiline input (4.0 and newer) store
+    /// the lines of the input separated by a `\n` character, the returned
+    /// string will contain those as is. Use `input_lines()` to get the lines
+    /// separately.
     pub fn input(&self) -> Cow<str> {
         self.get_string("input").unwrap()
     }
 
     /// Set the content of the buffer input.
+    ///
+    /// Newline characters are passed to Weechat as is, Weechat versions that
+    /// support multiline input will display the input over multiple lines.
     pub fn set_input(&self, input: &str) {
         self.set("input", input)
     }
 
+    /// Get the lines of the buffer input.
+    ///
+    /// The input is split on `\n` characters, a single line input returns a
+    /// single element.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// # let weechat = unsafe { Weechat::weechat() };
+    /// # let buffer = weechat.buffer_search("core", "weechat").unwrap();
+    /// buffer.set_input_multiline(&["first line", "second line"]);
+    ///
+    /// // Prints "first line" and "second line".
+    /// for line in buffer.input_lines() {
+    ///     buffer.print(&line);
+    /// }
+    /// ```
+    pub fn input_lines(&self) -> Vec<Cow<str>> {
+        split_input_lines(self.input())
+    }
+
+    /// Set the content of the buffer input to multiple lines.
+    ///
+    /// The lines are joined using a `\n` character, which is the separator
+    /// Weechat uses for multiline input. Lines which already contain a `\n`
+    /// character are passed as is, meaning that they will result in multiple
+    /// lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The lines that should be put into the input.
+    pub fn set_input_multiline(&self, lines: &[&str]) {
+        self.set_input(&join_input_lines(lines))
+    }
+
     /// Get the position of the cursor in the buffer input.
     pub fn input_position(&self) -> i32 {
         self.get_integer("input_pos")
@@ -1238,6 +1936,35 @@ impl Buffer<'_> {
         self.set("input_pos", &position.to_string())
     }
 
+    /// Set the content of the buffer input together with the cursor position.
+    ///
+    /// The input is set first and the position afterwards, the position is
+    /// clamped to the number of characters of the new input.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The new content of the buffer input.
+    ///
+    /// * `position` - The new position of the cursor, counted in characters,
+    ///     not in bytes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::buffer::BufferBuilder;
+    /// # let buffer_handle = BufferBuilder::new("test")
+    /// #    .build()
+    /// #    .unwrap();
+    /// # let buffer = buffer_handle.upgrade().unwrap();
+    ///
+    /// // The cursor will end up after the last character.
+    /// buffer.set_input_with_position("Grüße", 100);
+    /// ```
+    pub fn set_input_with_position(&self, input: &str, position: i32) {
+        self.set_input(input);
+        self.set_input_position(clamp_input_position(input, position));
+    }
+
     /// Enable multiline support.
     ///
     /// If enabled multiple lines separated by a newline are sent as a single
@@ -1260,11 +1987,64 @@ impl Buffer<'_> {
         self.get_integer("input_multiline") == 1
     }
 
+    /// Set if unknown commands should be sent to the input callback.
+    ///
+    /// By default a command like `/foo` that is typed into the buffer is
+    /// executed by Weechat, if the command doesn't exist Weechat prints an
+    /// error. If enabled, unknown commands are sent to the input callback of
+    /// the buffer instead, known commands are still executed by Weechat.
+    ///
+    /// # Arguments
+    ///
+    /// * `enable` - Should unknown commands be sent to the input callback.
+    pub fn set_input_get_unknown_commands(&self, enable: bool) {
+        self.set("input_get_unknown_commands", if enable { "1" } else { "0" });
+    }
+
+    /// Set if empty input should be sent to the input callback.
+    ///
+    /// By default pressing enter with an empty input line does nothing. If
+    /// enabled, the input callback is called with an empty string instead.
+    /// Older Weechat versions ignore this property.
+    ///
+    /// # Arguments
+    ///
+    /// * `enable` - Should empty input be sent to the input callback.
+    pub fn set_input_get_empty(&self, enable: bool) {
+        self.set("input_get_empty", if enable { "1" } else { "0" });
+    }
+
     /// Get the number of the buffer.
     pub fn number(&self) -> i32 {
         self.get_integer("number")
     }
 
+    /// Move the buffer to the given number.
+    ///
+    /// If another buffer already has the number, that buffer and all the
+    /// buffers after it are shifted by one. Numbers higher than the number of
+    /// the last buffer move the buffer to the end of the list.
+    ///
+    /// # Arguments
+    ///
+    /// * `number` - The number the buffer should be moved to.
+    pub fn move_to(&self, number: i32) {
+        self.set("number", &number.to_string());
+    }
+
+    /// Swap the number of this buffer with the number of another buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The buffer this buffer should be swapped with.
+    pub fn swap_with(&self, other: &Buffer) -> Result<(), ()> {
+        self.run_command(&format!(
+            "/buffer swap {} {}",
+            self.number(),
+            other.number()
+        ))
+    }
+
     /// Switch to the buffer
     pub fn switch_to(&self) {
         self.set("display", "1");
@@ -1285,6 +2065,40 @@ impl Buffer<'_> {
         }
     }
 
+    /// Is the buffer zoomed, i.e. is it the only displayed buffer of its
+    /// merged buffers.
+    pub fn is_zoomed(&self) -> bool {
+        self.get_integer("zoomed") == 1
+    }
+
+    /// Zoom on the buffer, only this buffer will be displayed instead of all
+    /// the buffers that it's merged with.
+    ///
+    /// Zooming only has an effect if the buffer is merged with other buffers.
+    /// This will switch to the buffer.
+    pub fn zoom(&self) -> Result<(), ()> {
+        self.switch_to();
+
+        if self.is_zoomed() {
+            Ok(())
+        } else {
+            self.run_command("/input zoom_merged_buffer")
+        }
+    }
+
+    /// Undo the zoom on the buffer, all the buffers that it's merged with will
+    /// be displayed again.
+    ///
+    /// This does nothing if the buffer isn't zoomed.
+    pub fn unzoom(&self) -> Result<(), ()> {
+        if self.is_zoomed() {
+            self.switch_to();
+            self.run_command("/input zoom_merged_buffer")
+        } else {
+            Ok(())
+        }
+    }
+
     /// Unmerge the buffer if it's merged with other buffers, the buffer will be
     /// moved to the current buffer number + 1.
     pub fn unmerge(&self) {
@@ -1362,6 +2176,29 @@ impl Buffer<'_> {
         }
     }
 
+    /// Get the number of lines of the buffer that are displayed.
+    ///
+    /// Unlike `num_lines()` this doesn't count lines that are hidden by a
+    /// filter (`/filter`).
+    pub fn displayed_lines(&self) -> i32 {
+        if !self.has_hidden_lines() {
+            return self.num_lines();
+        }
+
+        self.lines().filter(|line| line.displayed()).count() as i32
+    }
+
+    /// Does the buffer contain lines that are hidden by a filter.
+    pub fn has_hidden_lines(&self) -> bool {
+        let weechat = self.weechat();
+        let own_lines = self.own_lines();
+
+        unsafe {
+            let lines = weechat.hdata_get("lines");
+            weechat.hdata_integer(lines, own_lines, "lines_hidden") != 0
+        }
+    }
+
     /// Get the lines of the buffer.
     ///
     /// This returns an iterator over all the buffer lines, the iterator can be
@@ -1406,6 +2243,31 @@ impl Buffer<'_> {
         }
     }
 
+    /// Get an infolist containing a snapshot of the lines of the buffer.
+    ///
+    /// Unlike the lines returned by `lines()`, the infolist contains a copy
+    /// of the line data which isn't affected by later modifications of the
+    /// lines.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// # let weechat = unsafe { weechat::Weechat::weechat() };
+    /// # let buffer = weechat.current_buffer();
+    /// let infolist = buffer.line_infolist().unwrap();
+    ///
+    /// for item in infolist {
+    ///     if let Some(message) = item.get_string("message") {
+    ///         Weechat::print(&message);
+    ///     }
+    /// }
+    /// ```
+    pub fn line_infolist(&self) -> Result<Infolist, ()> {
+        self.weechat()
+            .get_infolist_with_pointer("buffer_lines", self.ptr() as *mut c_void, None)
+    }
+
     /// Get the window object that is currently displaying this buffer.
     ///
     /// Is `None` if no window is displaying this buffer.
@@ -1418,11 +2280,7 @@ impl Buffer<'_> {
         if ptr.is_null() {
             None
         } else {
-            Some(Window {
-                weechat: weechat.ptr,
-                ptr,
-                phantom: PhantomData,
-            })
+            Some(Window { weechat, ptr })
         }
     }
 
@@ -1436,3 +2294,89 @@ impl Buffer<'_> {
         self.set("hidden", "0");
     }
 }
+
+/// Clamp a cursor position, in characters, so it stays inside of the input.
+fn clamp_input_position(input: &str, position: i32) -> i32 {
+    let length = input.chars().count() as i32;
+
+    position.max(0).min(length)
+}
+
+/// Split a multiline input into its lines.
+fn split_input_lines(input: Cow<str>) -> Vec<Cow<str>> {
+    match input {
+        Cow::Borrowed(input) => input.split('\n').map(Cow::from).collect(),
+        Cow::Owned(input) => input
+            .split('\n')
+            .map(|line| Cow::from(line.to_owned()))
+            .collect(),
+    }
+}
+
+/// Join lines using the separator Weechat uses for multiline input.
+fn join_input_lines(lines: &[&str]) -> String {
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hotlist_priority_values() {
+        assert_eq!(HotlistPriority::Low.value(), "0");
+        assert_eq!(HotlistPriority::Message.value(), "1");
+        assert_eq!(HotlistPriority::Private.value(), "2");
+        assert_eq!(HotlistPriority::Highlight.value(), "3");
+    }
+
+    #[test]
+    fn input_lines_round_trip() {
+        let input = join_input_lines(&["first line", "second line"]);
+
+        assert_eq!(input, "first line\nsecond line");
+        assert_eq!(
+            split_input_lines(Cow::Borrowed(input.as_str())),
+            vec!["first line", "second line"]
+        );
+        assert_eq!(
+            split_input_lines(Cow::Owned(input.clone())),
+            vec!["first line", "second line"]
+        );
+    }
+
+    #[test]
+    fn input_lines_containing_newlines_are_split() {
+        let input = join_input_lines(&["first\nsecond", "third"]);
+
+        assert_eq!(
+            split_input_lines(Cow::Borrowed(input.as_str())),
+            vec!["first", "second", "third"]
+        );
+    }
+
+    #[test]
+    fn single_line_input() {
+        assert_eq!(split_input_lines(Cow::Borrowed("hello")), vec!["hello"]);
+        assert_eq!(split_input_lines(Cow::Borrowed("")), vec![""]);
+    }
+
+    #[test]
+    fn clamp_negative_input_position() {
+        assert_eq!(clamp_input_position("Grüße", -3), 0);
+    }
+
+    #[test]
+    fn clamp_input_position_inside_of_the_input() {
+        assert_eq!(clamp_input_position("Grüße", 0), 0);
+        assert_eq!(clamp_input_position("Grüße", 3), 3);
+        assert_eq!(clamp_input_position("Grüße", 5), 5);
+    }
+
+    #[test]
+    fn clamp_input_position_past_the_end() {
+        assert_eq!(clamp_input_position("Grüße", 6), 5);
+        assert_eq!(clamp_input_position("Grüße", 100), 5);
+        assert_eq!(clamp_input_position("", 1), 0);
+    }
+}
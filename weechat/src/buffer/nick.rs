@@ -108,6 +108,14 @@ impl<'a> Nick<'a> {
         }
     }
 
+    fn get_integer(&self, property: &str) -> i32 {
+        let weechat = self.get_weechat();
+        let get_integer = weechat.get().nicklist_nick_get_integer.unwrap();
+        let c_property = LossyCString::new(property);
+
+        unsafe { get_integer(self.buf_ptr, self.ptr, c_property.as_ptr()) }
+    }
+
     /// Get the name property of the nick.
     pub fn name(&self) -> Cow<str> {
         self.get_string("name").unwrap()
@@ -127,4 +135,80 @@ impl<'a> Nick<'a> {
     pub fn prefix_color(&self) -> Cow<str> {
         self.get_string("prefix_color").unwrap()
     }
+
+    /// Is the nick visible in the nicklist.
+    pub fn visible(&self) -> bool {
+        self.get_integer("visible") != 0
+    }
+
+    /// Set a property of the nick.
+    ///
+    /// The nick is updated in place, unlike removing and re-adding the nick
+    /// this keeps its position in the nicklist and doesn't cause the nicklist
+    /// to flicker.
+    ///
+    /// # Arguments
+    ///
+    /// * `property` - The name of the property that should be set, this can
+    ///     be one of color, prefix, prefix_color or visible.
+    ///
+    /// * `value` - The new value of the property.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// # let weechat = unsafe { Weechat::weechat() };
+    /// # let buffer = weechat.buffer_search("irc", "#weechat").unwrap();
+    /// if let Some(nick) = buffer.search_nick("alice") {
+    ///     nick.set_prefix("@");
+    ///     nick.set_prefix_color("lightgreen");
+    /// }
+    /// ```
+    pub fn set_property(&self, property: &str, value: &str) {
+        let weechat = self.get_weechat();
+        let nick_set = weechat.get().nicklist_nick_set.unwrap();
+
+        let property = LossyCString::new(property);
+        let value = LossyCString::new(value);
+
+        unsafe { nick_set(self.buf_ptr, self.ptr, property.as_ptr(), value.as_ptr()) };
+    }
+
+    /// Set the color of the nick.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The new color of the nick.
+    pub fn set_color(&self, color: &str) {
+        self.set_property("color", color)
+    }
+
+    /// Set the prefix of the nick.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The new prefix that is displayed before the nick in the
+    ///     nicklist.
+    pub fn set_prefix(&self, prefix: &str) {
+        self.set_property("prefix", prefix)
+    }
+
+    /// Set the color of the nick prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix_color` - The new color of the prefix.
+    pub fn set_prefix_color(&self, prefix_color: &str) {
+        self.set_property("prefix_color", prefix_color)
+    }
+
+    /// Set the visibility of the nick.
+    ///
+    /// # Arguments
+    ///
+    /// * `visible` - Should the nick be visible in the nicklist.
+    pub fn set_visible(&self, visible: bool) {
+        self.set_property("visible", if visible { "1" } else { "0" })
+    }
 }
@@ -1,6 +1,6 @@
-use std::{borrow::Cow, ffi::CStr, marker::PhantomData};
+use std::{borrow::Cow, ffi::CStr, iter, marker::PhantomData, os::raw::c_void};
 
-use weechat_sys::{t_gui_buffer, t_gui_nick_group, t_weechat_plugin};
+use weechat_sys::{t_gui_buffer, t_gui_nick, t_gui_nick_group, t_weechat_plugin};
 
 use crate::{
     buffer::{Buffer, Nick, NickSettings},
@@ -69,6 +69,73 @@ impl<'a> NickGroup<'a> {
         self.get_integer("level") as u32
     }
 
+    /// Get an iterator over the nicks that are direct children of this group.
+    ///
+    /// Nicks of subgroups aren't included, use `subgroups()` to walk the
+    /// groups below this one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// # let weechat = unsafe { Weechat::weechat() };
+    /// # let buffer = weechat.buffer_search("irc", "#weechat").unwrap();
+    /// for group in buffer.groups() {
+    ///     let nicks: Vec<String> = group.nicks().map(|n| n.name().to_string()).collect();
+    ///     Weechat::print(&format!("{}: {}", group.name(), nicks.join(", ")));
+    /// }
+    /// ```
+    pub fn nicks(&self) -> impl Iterator<Item = Nick<'a>> {
+        let weechat = self.get_weechat();
+        let buf_ptr = self.buf_ptr;
+        let weechat_ptr = self.weechat_ptr;
+
+        let (nick_hdata, first) = unsafe {
+            let group_hdata = weechat.hdata_get("nick_group");
+            (
+                weechat.hdata_get("nick"),
+                weechat.hdata_pointer(group_hdata, self.ptr as *mut c_void, "nicks"),
+            )
+        };
+
+        iter::successors(Some(first).filter(|p| !p.is_null()), move |nick| {
+            let next = unsafe { weechat.hdata_pointer(nick_hdata, *nick, "next_nick") };
+            Some(next).filter(|p| !p.is_null())
+        })
+        .map(move |nick| Nick {
+            ptr: nick as *mut t_gui_nick,
+            buf_ptr,
+            weechat_ptr,
+            buffer: PhantomData,
+        })
+    }
+
+    /// Get an iterator over the groups that are direct children of this group.
+    pub fn subgroups(&self) -> impl Iterator<Item = NickGroup<'a>> {
+        let weechat = self.get_weechat();
+        let buf_ptr = self.buf_ptr;
+        let weechat_ptr = self.weechat_ptr;
+
+        let (group_hdata, first) = unsafe {
+            let group_hdata = weechat.hdata_get("nick_group");
+            (
+                group_hdata,
+                weechat.hdata_pointer(group_hdata, self.ptr as *mut c_void, "children"),
+            )
+        };
+
+        iter::successors(Some(first).filter(|p| !p.is_null()), move |group| {
+            let next = unsafe { weechat.hdata_pointer(group_hdata, *group, "next_group") };
+            Some(next).filter(|p| !p.is_null())
+        })
+        .map(move |group| NickGroup {
+            ptr: group as *mut t_gui_nick_group,
+            buf_ptr,
+            weechat_ptr,
+            buffer: PhantomData,
+        })
+    }
+
     /// Create and add a new nick to the buffer nicklist under this group.
     ///
     /// # Arguments
@@ -0,0 +1,125 @@
+use std::{marker::PhantomData, ptr};
+
+use weechat_sys::{t_gui_buffer, t_gui_nick, t_gui_nick_group, t_weechat_plugin};
+
+use crate::{
+    buffer::{Buffer, Nick, NickGroup},
+    Weechat,
+};
+
+/// Walks over the nicklist of a buffer in the order Weechat displays it.
+///
+/// Groups are traversed depth-first, every group is followed by its nicks and
+/// then by its subgroups.
+struct NicklistItems<'a> {
+    weechat_ptr: *mut t_weechat_plugin,
+    buf_ptr: *mut t_gui_buffer,
+    group: *mut t_gui_nick_group,
+    nick: *mut t_gui_nick,
+    done: bool,
+    buffer: PhantomData<&'a Buffer<'a>>,
+}
+
+impl<'a> NicklistItems<'a> {
+    fn new(weechat_ptr: *mut t_weechat_plugin, buf_ptr: *mut t_gui_buffer) -> Self {
+        NicklistItems {
+            weechat_ptr,
+            buf_ptr,
+            group: ptr::null_mut(),
+            nick: ptr::null_mut(),
+            done: false,
+            buffer: PhantomData,
+        }
+    }
+
+    /// Step to the next nicklist item.
+    ///
+    /// Returns the current group and nick pointers, the nick pointer is null if
+    /// the item is a group.
+    fn step(&mut self) -> Option<(*mut t_gui_nick_group, *mut t_gui_nick)> {
+        if self.done {
+            return None;
+        }
+
+        let weechat = Weechat::from_ptr(self.weechat_ptr);
+        let get_next_item = weechat.get().nicklist_get_next_item.unwrap();
+
+        unsafe { get_next_item(self.buf_ptr, &mut self.group, &mut self.nick) };
+
+        if self.group.is_null() && self.nick.is_null() {
+            self.done = true;
+            None
+        } else {
+            Some((self.group, self.nick))
+        }
+    }
+}
+
+/// An iterator over the nicks of a buffer nicklist.
+pub struct NickIterator<'a> {
+    items: NicklistItems<'a>,
+}
+
+impl<'a> NickIterator<'a> {
+    pub(crate) fn new(weechat_ptr: *mut t_weechat_plugin, buf_ptr: *mut t_gui_buffer) -> Self {
+        NickIterator {
+            items: NicklistItems::new(weechat_ptr, buf_ptr),
+        }
+    }
+}
+
+impl<'a> Iterator for NickIterator<'a> {
+    type Item = Nick<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (_, nick) = self.items.step()?;
+
+            if !nick.is_null() {
+                return Some(Nick {
+                    ptr: nick,
+                    buf_ptr: self.items.buf_ptr,
+                    weechat_ptr: self.items.weechat_ptr,
+                    buffer: PhantomData,
+                });
+            }
+        }
+    }
+}
+
+/// An iterator over the groups of a buffer nicklist.
+pub struct NickGroupIterator<'a> {
+    items: NicklistItems<'a>,
+}
+
+impl<'a> NickGroupIterator<'a> {
+    pub(crate) fn new(weechat_ptr: *mut t_weechat_plugin, buf_ptr: *mut t_gui_buffer) -> Self {
+        NickGroupIterator {
+            items: NicklistItems::new(weechat_ptr, buf_ptr),
+        }
+    }
+}
+
+impl<'a> Iterator for NickGroupIterator<'a> {
+    type Item = NickGroup<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (group, nick) = self.items.step()?;
+
+            if nick.is_null() {
+                let group = NickGroup {
+                    ptr: group,
+                    buf_ptr: self.items.buf_ptr,
+                    weechat_ptr: self.items.weechat_ptr,
+                    buffer: PhantomData,
+                };
+
+                // The root group isn't displayed by Weechat, skip it.
+                if group.level() != 0 {
+                    return Some(group);
+                }
+            }
+        }
+    }
+}
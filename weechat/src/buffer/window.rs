@@ -1,6 +1,6 @@
-use std::{marker::PhantomData, ptr};
+use std::ptr;
 
-use weechat_sys::{t_gui_window, t_weechat_plugin};
+use weechat_sys::{t_gui_buffer, t_gui_window};
 
 use super::Buffer;
 use crate::{LossyCString, Weechat};
@@ -10,15 +10,50 @@ use crate::{LossyCString, Weechat};
 /// A window is a screen area which displays a buffer. It is possible to split
 /// your screen into many windows.
 pub struct Window<'a> {
-    pub(crate) weechat: *mut t_weechat_plugin,
+    pub(crate) weechat: &'a Weechat,
     pub(crate) ptr: *mut t_gui_window,
-    pub(crate) phantom: PhantomData<&'a Buffer<'a>>,
+}
+
+impl Weechat {
+    /// Get the current window.
+    pub fn current_window(&self) -> Option<Window> {
+        let window_get_pointer = self.get().window_get_pointer.unwrap();
+        let property = LossyCString::new("current");
+
+        let ptr = unsafe { window_get_pointer(ptr::null_mut(), property.as_ptr()) };
+
+        if ptr.is_null() {
+            None
+        } else {
+            Some(self.window_from_ptr(ptr as *mut t_gui_window))
+        }
+    }
+
+    /// Get all the windows, ordered by their number.
+    pub fn windows(&self) -> Vec<Window> {
+        let mut windows = Vec::new();
+
+        unsafe {
+            let hdata = self.hdata_get("window");
+            let mut ptr = self.hdata_get_list(hdata, "gui_windows");
+
+            while !ptr.is_null() {
+                windows.push(self.window_from_ptr(ptr as *mut t_gui_window));
+                ptr = self.hdata_move(hdata, ptr, 1);
+            }
+        }
+
+        windows
+    }
+
+    pub(crate) fn window_from_ptr(&self, ptr: *mut t_gui_window) -> Window {
+        Window { weechat: self, ptr }
+    }
 }
 
 impl<'a> Window<'a> {
     fn get_integer(&self, property: &str) -> i32 {
-        let weechat = Weechat::from_ptr(self.weechat);
-        let get_integer = weechat.get().window_get_integer.unwrap();
+        let get_integer = self.weechat.get().window_get_integer.unwrap();
         let property = LossyCString::new(property);
 
         unsafe { get_integer(self.ptr, property.as_ptr()) }
@@ -109,9 +144,18 @@ impl<'a> Window<'a> {
         self.get_integer("lines_after")
     }
 
+    /// The buffer that is displayed in the window.
+    pub fn buffer(&self) -> Buffer<'a> {
+        let window_get_pointer = self.weechat.get().window_get_pointer.unwrap();
+        let property = LossyCString::new("buffer");
+
+        let ptr = unsafe { window_get_pointer(self.ptr, property.as_ptr()) };
+
+        self.weechat.buffer_from_ptr(ptr as *mut t_gui_buffer)
+    }
+
     fn set_title_helper(&self, title: Option<&str>) {
-        let weechat = Weechat::from_ptr(self.weechat);
-        let set_title = weechat.get().window_set_title.unwrap();
+        let set_title = self.weechat.get().window_set_title.unwrap();
 
         if let Some(title) = title {
             let title = LossyCString::new(title);
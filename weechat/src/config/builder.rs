@@ -0,0 +1,249 @@
+use crate::config::{
+    BooleanOptionSettings, ColorOptionSettings, Config, ConfigReloadCallback, ConfigSection,
+    ConfigSectionSettings, IntegerOptionSettings, StringOptionSettings,
+};
+
+type OptionCreator = Box<dyn FnOnce(&mut ConfigSection) -> Result<(), ()>>;
+
+/// Builder to declare a whole configuration file in a single expression.
+///
+/// The builder collects the sections and options and creates them in the
+/// order they were declared once `build()` is called.
+///
+/// # Examples
+///
+/// ```no_run
+/// use weechat::config::ConfigBuilder;
+///
+/// let config = ConfigBuilder::new("ripgrep")
+///     .section("main", |section| {
+///         section
+///             .boolean("go_to_buffer", true, "Go to the grep buffer when the search is over")
+///             .integer("max_matches", 100, 0, 10000, "Maximum number of matches to display");
+///     })
+///     .section("color", |section| {
+///         section.color("match", "lightred", "Color of the matched text");
+///     })
+///     .build()
+///     .expect("Can't create the ripgrep config");
+/// ```
+pub struct ConfigBuilder {
+    name: String,
+    reload_callback: Option<Box<dyn ConfigReloadCallback>>,
+    sections: Vec<SectionBuilder>,
+}
+
+/// Builder for the options of a single config section.
+///
+/// Section builders are handed out by `ConfigBuilder::section()`.
+pub struct SectionBuilder {
+    settings: ConfigSectionSettings,
+    options: Vec<OptionCreator>,
+}
+
+impl ConfigBuilder {
+    /// Create a new config builder.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the configuration file.
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        ConfigBuilder {
+            name: name.into(),
+            reload_callback: None,
+            sections: Vec::new(),
+        }
+    }
+
+    /// Set the callback that will be called when the configuration file is
+    /// reloaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The reload callback.
+    pub fn reload_callback(mut self, callback: impl ConfigReloadCallback) -> Self {
+        self.reload_callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Declare a new section.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the section.
+    ///
+    /// * `options` - A function that declares the options of the section.
+    pub fn section<N: Into<String>>(
+        self,
+        name: N,
+        options: impl FnOnce(&mut SectionBuilder),
+    ) -> Self {
+        self.section_with_settings(ConfigSectionSettings::new(name), options)
+    }
+
+    /// Declare a new section using custom section settings.
+    ///
+    /// This can be used to set read and write callbacks for the section.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - The settings of the section.
+    ///
+    /// * `options` - A function that declares the options of the section.
+    pub fn section_with_settings(
+        mut self,
+        settings: ConfigSectionSettings,
+        options: impl FnOnce(&mut SectionBuilder),
+    ) -> Self {
+        let mut section = SectionBuilder {
+            settings,
+            options: Vec::new(),
+        };
+
+        options(&mut section);
+        self.sections.push(section);
+        self
+    }
+
+    /// Create the configuration file with all the declared sections and
+    /// options.
+    ///
+    /// Returns an empty error if the config, one of the sections or one of
+    /// the options couldn't be created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn build(self) -> Result<Config, ()> {
+        let mut config = Config::config_new_helper(&self.name, self.reload_callback)?;
+
+        for section in self.sections {
+            let mut handle = config.new_section(section.settings)?;
+
+            for create_option in section.options {
+                create_option(&mut *handle)?;
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+impl SectionBuilder {
+    fn push(&mut self, creator: impl FnOnce(&mut ConfigSection) -> Result<(), ()> + 'static) {
+        self.options.push(Box::new(creator));
+    }
+
+    /// Declare a new boolean option.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the option.
+    ///
+    /// * `default_value` - The default value of the option.
+    ///
+    /// * `description` - The description of the option.
+    pub fn boolean(&mut self, name: &str, default_value: bool, description: &str) -> &mut Self {
+        self.boolean_option(
+            BooleanOptionSettings::new(name)
+                .default_value(default_value)
+                .description(description),
+        )
+    }
+
+    /// Declare a new integer option.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the option.
+    ///
+    /// * `default_value` - The default value of the option.
+    ///
+    /// * `min` - The minimal value of the option.
+    ///
+    /// * `max` - The maximal value of the option.
+    ///
+    /// * `description` - The description of the option.
+    pub fn integer(
+        &mut self,
+        name: &str,
+        default_value: i32,
+        min: i32,
+        max: i32,
+        description: &str,
+    ) -> &mut Self {
+        self.integer_option(
+            IntegerOptionSettings::new(name)
+                .default_value(default_value)
+                .min(min)
+                .max(max)
+                .description(description),
+        )
+    }
+
+    /// Declare a new string option.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the option.
+    ///
+    /// * `default_value` - The default value of the option.
+    ///
+    /// * `description` - The description of the option.
+    pub fn string(&mut self, name: &str, default_value: &str, description: &str) -> &mut Self {
+        self.string_option(
+            StringOptionSettings::new(name)
+                .default_value(default_value)
+                .description(description),
+        )
+    }
+
+    /// Declare a new color option.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the option.
+    ///
+    /// * `default_value` - The default color of the option.
+    ///
+    /// * `description` - The description of the option.
+    pub fn color(&mut self, name: &str, default_value: &str, description: &str) -> &mut Self {
+        self.color_option(
+            ColorOptionSettings::new(name)
+                .default_value(default_value)
+                .description(description),
+        )
+    }
+
+    /// Declare a new boolean option using the given settings.
+    ///
+    /// This can be used to set a change callback for the option.
+    pub fn boolean_option(&mut self, settings: BooleanOptionSettings) -> &mut Self {
+        self.push(move |section| section.new_boolean_option(settings).map(|_| ()));
+        self
+    }
+
+    /// Declare a new integer option using the given settings.
+    ///
+    /// This can be used to set a change callback for the option.
+    pub fn integer_option(&mut self, settings: IntegerOptionSettings) -> &mut Self {
+        self.push(move |section| section.new_integer_option(settings).map(|_| ()));
+        self
+    }
+
+    /// Declare a new string option using the given settings.
+    ///
+    /// This can be used to set a change or check callback for the option.
+    pub fn string_option(&mut self, settings: StringOptionSettings) -> &mut Self {
+        self.push(move |section| section.new_string_option(settings).map(|_| ()));
+        self
+    }
+
+    /// Declare a new color option using the given settings.
+    ///
+    /// This can be used to set a change callback for the option.
+    pub fn color_option(&mut self, settings: ColorOptionSettings) -> &mut Self {
+        self.push(move |section| section.new_color_option(settings).map(|_| ()));
+        self
+    }
+}
@@ -97,6 +97,19 @@ impl<'a> ColorOption<'a> {
             CStr::from_ptr(string).to_string_lossy()
         }
     }
+
+    /// Get the color code of the option value, ready to be used for display.
+    ///
+    /// This is the same as passing the value of the option to
+    /// `Weechat::color()`, an empty string is returned if the value isn't a
+    /// valid color.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn color_code(&self) -> String {
+        Weechat::color(&self.value()).to_string()
+    }
 }
 
 impl<'a> FromPtrs for ColorOption<'a> {
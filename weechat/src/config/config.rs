@@ -235,7 +235,7 @@ impl Config {
         Config::config_new_helper(name, Some(callback))
     }
 
-    fn config_new_helper(
+    pub(crate) fn config_new_helper(
         name: &str,
         callback: Option<Box<dyn ConfigReloadCallback>>,
     ) -> Result<Config, ()> {
@@ -347,6 +347,20 @@ impl Config {
         }
     }
 
+    fn write_return_value_to_error(return_value: c_int) -> std::io::Result<()> {
+        match return_value {
+            weechat_sys::WEECHAT_CONFIG_WRITE_OK => Ok(()),
+            weechat_sys::WEECHAT_CONFIG_WRITE_ERROR => Err(IoError::new(
+                ErrorKind::Other,
+                "Error writing the configuration file",
+            )),
+            weechat_sys::WEECHAT_CONFIG_WRITE_MEMORY_ERROR => {
+                Err(IoError::new(ErrorKind::Other, "Not enough memory"))
+            }
+            _ => unreachable!(),
+        }
+    }
+
     /// Read the configuration file from the disk.
     pub fn read(&self) -> std::io::Result<()> {
         let weechat = Weechat::from_ptr(self.inner.weechat_ptr);
@@ -357,14 +371,31 @@ impl Config {
         Config::return_value_to_error(ret)
     }
 
+    /// Reload the configuration file from the disk.
+    ///
+    /// This runs the reload callback of the config if one was set, otherwise
+    /// all options are reset to their default values and the file is read
+    /// again.
+    pub fn reload(&self) -> std::io::Result<()> {
+        let weechat = Weechat::from_ptr(self.inner.weechat_ptr);
+        let config_reload = weechat.get().config_reload.unwrap();
+
+        let ret = unsafe { config_reload(self.inner.ptr) };
+
+        Config::return_value_to_error(ret)
+    }
+
     /// Write the configuration file to the disk.
+    ///
+    /// The write callbacks of the sections will be run while the file is
+    /// written, sections without a write callback are written out as is.
     pub fn write(&self) -> std::io::Result<()> {
         let weechat = Weechat::from_ptr(self.inner.weechat_ptr);
         let config_write = weechat.get().config_write.unwrap();
 
         let ret = unsafe { config_write(self.inner.ptr) };
 
-        Config::return_value_to_error(ret)
+        Config::write_return_value_to_error(ret)
     }
 
     /// Create a new section in the configuration file.
@@ -584,6 +615,33 @@ impl Config {
         }
     }
 
+    /// Get an iterator over all the sections of the configuration object.
+    ///
+    /// The sections are returned in no particular order.
+    ///
+    /// # Panics
+    ///
+    /// The iterator will panic if it is being used in a section read/write
+    /// callback or if one of the sections is already mutably borrowed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// # use weechat::config::{BaseConfigOption, Config};
+    /// # let config = Config::new("my_plugin").unwrap();
+    /// for section in config.sections() {
+    ///     for option in section.options() {
+    ///         Weechat::print(&format!("{}.{}", section.name(), option.name()));
+    ///     }
+    /// }
+    /// ```
+    pub fn sections(&self) -> impl Iterator<Item = SectionHandle> {
+        self.sections.values().map(|section| SectionHandle {
+            inner: section.borrow(),
+        })
+    }
+
     /// Search the configuration object for a section and borrow it mutably.
     ///
     /// Returns a handle to a section if one is found, None otherwise.
@@ -656,3 +714,22 @@ impl Conf {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_return_values() {
+        assert!(Config::write_return_value_to_error(weechat_sys::WEECHAT_CONFIG_WRITE_OK).is_ok());
+
+        let error = Config::write_return_value_to_error(weechat_sys::WEECHAT_CONFIG_WRITE_ERROR)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Error writing the configuration file");
+
+        let error =
+            Config::write_return_value_to_error(weechat_sys::WEECHAT_CONFIG_WRITE_MEMORY_ERROR)
+                .unwrap_err();
+        assert_eq!(error.to_string(), "Not enough memory");
+    }
+}
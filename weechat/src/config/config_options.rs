@@ -1,4 +1,4 @@
-use crate::{config::OptionChanged, LossyCString, Weechat};
+use crate::{config::OptionChanged, infolist::InfolistVariable, LossyCString, Weechat};
 use std::{borrow::Cow, convert::TryFrom, ffi::CStr};
 use weechat_sys::{t_config_option, t_weechat_plugin};
 
@@ -67,6 +67,34 @@ pub trait HidenConfigOptionT {
             }
         }
     }
+
+    /// Get variables of the `option` infolist item for this option.
+    ///
+    /// Returns None if the infolist item can't be fetched, a variable is
+    /// None if it doesn't exist.
+    fn get_infolist_variables(&self, variables: &[&str]) -> Option<Vec<Option<String>>> {
+        let option_name = format!(
+            "{}.{}.{}",
+            self.get_string("config_name")?,
+            self.get_string("section_name")?,
+            self.get_string("name")?
+        );
+
+        let weechat = self.get_weechat();
+        let mut infolist = weechat.get_infolist("option", Some(&option_name)).ok()?;
+        let item = infolist.next()?;
+
+        let values = variables
+            .iter()
+            .map(|variable| match item.get(variable)? {
+                InfolistVariable::String(string) => Some(string.to_string()),
+                InfolistVariable::Integer(integer) => Some(integer.to_string()),
+                _ => None,
+            })
+            .collect();
+
+        Some(values)
+    }
 }
 
 /// Base configuration option methods.
@@ -86,6 +114,19 @@ pub trait BaseConfigOption: HidenConfigOptionT {
             .expect("Can't get the description of the option")
     }
 
+    /// Get the default value of the option as a string.
+    ///
+    /// Returns an empty string if the option has no default value.
+    fn default_value_string(&self) -> Cow<str> {
+        let value = self
+            .get_infolist_variables(&["default_value"])
+            .and_then(|mut values| values.pop())
+            .flatten()
+            .unwrap_or_default();
+
+        Cow::from(value)
+    }
+
     /// Get the section name of the section the option belongs to.
     fn section_name(&self) -> Cow<str> {
         self.get_string("section_name")
@@ -107,6 +148,12 @@ pub trait BaseConfigOption: HidenConfigOptionT {
     }
 
     /// Resets the option to its default value.
+    ///
+    /// # Arguments
+    ///
+    /// * `run_callback` - Should the change callback of the option be run if
+    ///     the value of the option changes, can be disabled to reset many
+    ///     options at once without running the callbacks.
     fn reset(&self, run_callback: bool) -> OptionChanged {
         let weechat = self.get_weechat();
         let option_reset = weechat.get().config_option_reset.unwrap();
@@ -134,6 +181,36 @@ pub trait BaseConfigOption: HidenConfigOptionT {
         OptionChanged::from_int(ret)
     }
 
+    /// Set the option using a string and run the change callback.
+    ///
+    /// This works for every option type, Weechat parses the string depending
+    /// on the option type. Boolean options accept `on`, `off` and `toggle`,
+    /// integer options accept a number or a relative change like `++1` or
+    /// `--1`.
+    ///
+    /// # Arguments
+    /// `value` - The value to which the option should be set.
+    fn set_value(&self, value: &str) -> OptionChanged {
+        self.set(value, true)
+    }
+
+    /// Is the option currently set to its default value.
+    fn is_default(&self) -> bool {
+        let values = self.get_infolist_variables(&[
+            "value",
+            "default_value",
+            "value_is_null",
+            "default_value_is_null",
+        ]);
+
+        match values.as_deref() {
+            Some([value, default_value, value_is_null, default_is_null]) => {
+                value == default_value && value_is_null == default_is_null
+            }
+            _ => false,
+        }
+    }
+
     /// Is the option undefined/null.
     fn is_null(&self) -> bool {
         let weechat = self.get_weechat();
@@ -0,0 +1,150 @@
+use crate::{
+    config::{
+        config_options::{ConfigOptions, FromPtrs, HidenConfigOptionT},
+        BaseConfigOption, IntegerOption,
+    },
+    Weechat,
+};
+use std::marker::PhantomData;
+use strum::VariantNames;
+use weechat_sys::{t_config_option, t_weechat_plugin};
+
+/// Settings for a new enum option.
+///
+/// Enum options are integer options that take the names of the enum variants
+/// as their symbolic values.
+pub struct EnumOptionSettings<T: VariantNames + From<i32> + PartialEq + 'static> {
+    pub(crate) name: String,
+
+    pub(crate) description: String,
+
+    pub(crate) default_value: Option<T>,
+
+    #[allow(clippy::type_complexity)]
+    pub(crate) change_cb: Option<Box<dyn FnMut(&Weechat, &EnumOption<T>)>>,
+}
+
+impl<T: VariantNames + From<i32> + PartialEq + 'static> EnumOptionSettings<T> {
+    /// Create new settings that can be used to create a new enum option.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the new option.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use strum_macros::EnumVariantNames;
+    /// use weechat::config::EnumOptionSettings;
+    ///
+    /// #[derive(EnumVariantNames, PartialEq)]
+    /// #[strum(serialize_all = "kebab_case")]
+    /// pub enum ServerBufferMerge {
+    ///     MergeWithCore,
+    ///     Independent,
+    /// }
+    ///
+    /// impl From<i32> for ServerBufferMerge {
+    ///     fn from(value: i32) -> Self {
+    ///         match value {
+    ///             1 => ServerBufferMerge::Independent,
+    ///             _ => ServerBufferMerge::MergeWithCore,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let settings = EnumOptionSettings::new("server_buffer")
+    ///     .description("Should the server buffer be merged with the core buffer")
+    ///     .default_value(ServerBufferMerge::Independent);
+    /// ```
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        EnumOptionSettings {
+            name: name.into(),
+            description: String::new(),
+            default_value: None,
+            change_cb: None,
+        }
+    }
+
+    /// Set the description of the option.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - The description of the new option.
+    pub fn description<D: Into<String>>(mut self, descritpion: D) -> Self {
+        self.description = descritpion.into();
+        self
+    }
+
+    /// Set the default value of the option.
+    ///
+    /// This is the value the option will have if it isn't set by the user. If
+    /// the option is reset, the option will take this value. Defaults to the
+    /// first variant of the enum.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value that should act as the default value.
+    pub fn default_value(mut self, value: T) -> Self {
+        self.default_value = Some(value);
+        self
+    }
+
+    /// Set the callback that will run when the value of the option changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback that will be run.
+    pub fn set_change_callback(
+        mut self,
+        callback: impl FnMut(&Weechat, &EnumOption<T>) + 'static,
+    ) -> Self {
+        self.change_cb = Some(Box::new(callback));
+        self
+    }
+
+    /// Get the name of the variant that acts as the default value.
+    pub(crate) fn default_variant(&self) -> &'static str {
+        let index = self
+            .default_value
+            .as_ref()
+            .and_then(|default| (0..T::VARIANTS.len()).find(|i| T::from(*i as i32) == *default))
+            .unwrap_or(0);
+
+        T::VARIANTS.get(index).copied().unwrap_or_default()
+    }
+}
+
+/// A config option with a enum value.
+pub struct EnumOption<'a, T> {
+    pub(crate) inner: IntegerOption<'a>,
+    pub(crate) _enum: PhantomData<T>,
+}
+
+impl<'a, T: From<i32>> EnumOption<'a, T> {
+    /// Get the value of the option.
+    pub fn value(&self) -> T {
+        T::from(self.inner.value())
+    }
+}
+
+impl<'a, T> FromPtrs for EnumOption<'a, T> {
+    fn from_ptrs(option_ptr: *mut t_config_option, weechat_ptr: *mut t_weechat_plugin) -> Self {
+        EnumOption {
+            inner: IntegerOption::from_ptrs(option_ptr, weechat_ptr),
+            _enum: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> HidenConfigOptionT for EnumOption<'a, T> {
+    fn get_ptr(&self) -> *mut t_config_option {
+        self.inner.get_ptr()
+    }
+
+    fn get_weechat(&self) -> Weechat {
+        self.inner.get_weechat()
+    }
+}
+
+impl<'a, T> BaseConfigOption for EnumOption<'a, T> {}
+impl<'a, T> ConfigOptions for EnumOption<'_, T> {}
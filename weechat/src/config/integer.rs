@@ -149,6 +149,24 @@ impl<'a> IntegerOption<'a> {
         let config_integer = weechat.get().config_integer.unwrap();
         unsafe { config_integer(self.get_ptr()) }
     }
+
+    /// Get the minimal value of the option.
+    pub fn min(&self) -> i32 {
+        self.get_integer_variable("min")
+    }
+
+    /// Get the maximal value of the option.
+    pub fn max(&self) -> i32 {
+        self.get_integer_variable("max")
+    }
+
+    fn get_integer_variable(&self, variable: &str) -> i32 {
+        self.get_infolist_variables(&[variable])
+            .and_then(|mut values| values.pop())
+            .flatten()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
 }
 
 impl<'a> FromPtrs for IntegerOption<'a> {
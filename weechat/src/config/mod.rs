@@ -25,22 +25,29 @@
 //! ```
 
 mod boolean;
+mod builder;
 mod color;
 #[allow(clippy::module_inception)]
 mod config;
 mod config_options;
+#[cfg(feature = "config_macro")]
+mod enum_option;
 mod integer;
 mod section;
 mod string;
 
 pub use crate::config::{
     boolean::{BooleanOption, BooleanOptionSettings},
+    builder::{ConfigBuilder, SectionBuilder},
     color::{ColorOption, ColorOptionSettings},
     config::{Conf, Config, ConfigReloadCallback, OptionChanged},
     integer::{IntegerOption, IntegerOptionSettings},
-    string::{StringOption, StringOptionSettings},
+    string::{SecureStringOptionSettings, StringOption, StringOptionSettings},
 };
 
+#[cfg(feature = "config_macro")]
+pub use crate::config::enum_option::{EnumOption, EnumOptionSettings};
+
 pub use crate::config::{
     config_options::{BaseConfigOption, ConfigOptions, OptionType},
     section::{
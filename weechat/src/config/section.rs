@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     cell::{Ref, RefCell, RefMut},
     collections::HashMap,
     ffi::CStr,
@@ -16,11 +17,14 @@ use crate::{
         config_options::{CheckCB, OptionPointers, OptionType},
         BaseConfigOption, BooleanOption, BooleanOptionSettings, ColorOption, ColorOptionSettings,
         Conf, Config, ConfigOptions, IntegerOption, IntegerOptionSettings, OptionChanged,
-        StringOption, StringOptionSettings,
+        SecureStringOptionSettings, StringOption, StringOptionSettings,
     },
     LossyCString, Weechat,
 };
 
+#[cfg(feature = "config_macro")]
+use crate::config::{config_options::FromPtrs, EnumOption, EnumOptionSettings};
+
 #[derive(Default)]
 struct OptionDescription<'a> {
     pub name: &'a str,
@@ -51,6 +55,26 @@ impl<'a> ConfigOption<'a> {
             ConfigOption::String(ref o) => o,
         }
     }
+
+    /// Get the minimal value of the option.
+    ///
+    /// Returns None if the option isn't an integer option.
+    pub fn min(&self) -> Option<i32> {
+        match self {
+            ConfigOption::Integer(ref o) => Some(o.min()),
+            _ => None,
+        }
+    }
+
+    /// Get the maximal value of the option.
+    ///
+    /// Returns None if the option isn't an integer option.
+    pub fn max(&self) -> Option<i32> {
+        match self {
+            ConfigOption::Integer(ref o) => Some(o.max()),
+            _ => None,
+        }
+    }
 }
 
 impl<'a> Deref for ConfigOption<'a> {
@@ -363,22 +387,7 @@ impl Drop for ConfigSection {
         let section_free = weechat.get().config_section_free.unwrap();
 
         for (_, option_ptrs) in self.option_pointers.drain() {
-            unsafe {
-                match option_ptrs {
-                    ConfigOptionPointers::Integer(p) => {
-                        Box::from_raw(p as *mut OptionPointers<IntegerOption>);
-                    }
-                    ConfigOptionPointers::Boolean(p) => {
-                        Box::from_raw(p as *mut OptionPointers<BooleanOption>);
-                    }
-                    ConfigOptionPointers::String(p) => {
-                        Box::from_raw(p as *mut OptionPointers<StringOption>);
-                    }
-                    ConfigOptionPointers::Color(p) => {
-                        Box::from_raw(p as *mut OptionPointers<ColorOption>);
-                    }
-                }
-            }
+            unsafe { ConfigSection::free_option_pointers(option_ptrs) }
         }
 
         unsafe {
@@ -419,12 +428,37 @@ type WeechatOptCheckCbT = unsafe extern "C" fn(
 ) -> c_int;
 
 impl ConfigSection {
+    /// Free the callback data of an option.
+    ///
+    /// # Safety
+    ///
+    /// The option must already be freed, otherwise Weechat may call the
+    /// callbacks of the option using the freed data.
+    unsafe fn free_option_pointers(option_ptrs: ConfigOptionPointers) {
+        match option_ptrs {
+            ConfigOptionPointers::Integer(p) => {
+                Box::from_raw(p as *mut OptionPointers<IntegerOption>);
+            }
+            ConfigOptionPointers::Boolean(p) => {
+                Box::from_raw(p as *mut OptionPointers<BooleanOption>);
+            }
+            ConfigOptionPointers::String(p) => {
+                Box::from_raw(p as *mut OptionPointers<StringOption>);
+            }
+            ConfigOptionPointers::Color(p) => {
+                Box::from_raw(p as *mut OptionPointers<ColorOption>);
+            }
+        }
+    }
+
     /// Get the name of the section.
     pub fn name(&self) -> &str {
         &self.name
     }
 
     /// Get the config options of this section.
+    ///
+    /// The options are returned in no particular order.
     pub fn options(&self) -> Vec<ConfigOption> {
         self.option_pointers
             .keys()
@@ -436,17 +470,21 @@ impl ConfigSection {
     ///
     /// Returns an Err if the option can't be found in this section.
     ///
+    /// Weechat doesn't run any callbacks of the option when it's freed, the
+    /// options created by this crate don't have a delete callback.
+    ///
     /// # Arguments
     ///
     /// * `option_name` - The name of the option that should be freed.
     pub fn free_option(&mut self, option_name: &str) -> Result<(), ()> {
         let weechat = Weechat::from_ptr(self.weechat_ptr);
 
-        let option_pointers = self.option_pointers.remove(option_name);
-        if option_pointers.is_none() {
+        let option_pointers = if let Some(pointers) = self.option_pointers.remove(option_name) {
+            pointers
+        } else {
             // TODO Return a better error value here.
             return Err(());
-        }
+        };
 
         let option = self
             .search_option(option_name)
@@ -454,11 +492,74 @@ impl ConfigSection {
 
         let config_option_free = weechat.get().config_option_free.unwrap();
 
-        unsafe { config_option_free(option.get_ptr()) }
+        unsafe {
+            config_option_free(option.get_ptr());
+            ConfigSection::free_option_pointers(option_pointers);
+        }
 
         Ok(())
     }
 
+    /// Rename a config option that belongs to this section.
+    ///
+    /// If no option with the new name exists the option is renamed, it keeps
+    /// its value and its callbacks. If an option with the new name already
+    /// exists, the value of the old option is set on the new option, running
+    /// its change callback, and the old option is freed.
+    ///
+    /// Returns an Err if the old option can't be found in this section or if
+    /// the value couldn't be set on the new option.
+    ///
+    /// When called from a section read callback, lines of the config file that
+    /// are read afterwards are matched against the new name. The config file
+    /// keeps using the old name until the config is written. Options that
+    /// aren't declared anymore can't be renamed, to migrate such an option
+    /// set the new option from the section read callback when the old name is
+    /// read.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_name` - The current name of the option.
+    ///
+    /// * `new_name` - The new name of the option.
+    pub fn rename_option(&mut self, old_name: &str, new_name: &str) -> Result<(), ()> {
+        if !self.option_pointers.contains_key(old_name) {
+            return Err(());
+        }
+
+        let old_option = self.search_option(old_name).ok_or(())?;
+
+        if let Some(new_option) = self.search_option(new_name) {
+            let value = old_option
+                .get_infolist_variables(&["value"])
+                .and_then(|mut values| values.pop())
+                .flatten()
+                .ok_or(())?;
+
+            match new_option.set(&value, true) {
+                OptionChanged::Changed | OptionChanged::Unchanged => (),
+                _ => return Err(()),
+            }
+
+            self.free_option(old_name)
+        } else {
+            let weechat = Weechat::from_ptr(self.weechat_ptr);
+            let config_option_rename = weechat.get().config_option_rename.unwrap();
+
+            let name = LossyCString::new(new_name);
+            unsafe { config_option_rename(old_option.get_ptr(), name.as_ptr()) };
+
+            let option_pointers = self
+                .option_pointers
+                .remove(old_name)
+                .expect("No option pointers found even though they were checked");
+            self.option_pointers
+                .insert(new_name.to_owned(), option_pointers);
+
+            Ok(())
+        }
+    }
+
     /// Search for an option in this section.
     /// # Arguments
     ///
@@ -483,6 +584,53 @@ impl ConfigSection {
         ))
     }
 
+    /// Set the values of many options of this section at once.
+    ///
+    /// Every option is set using its string setter, the same way
+    /// `set_value()` does it, which runs the change callback of options
+    /// whose value changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - A map from option names to the new values of the options.
+    ///
+    /// Returns a map from option names to the result of setting the option.
+    /// Options that don't exist in this section are reported as
+    /// `OptionChanged::NotFound`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use weechat::Weechat;
+    /// # use weechat::config::{Config, OptionChanged};
+    /// # let config = Config::new("my_plugin").unwrap();
+    /// # let section = config.search_section("look").unwrap();
+    /// let mut values = HashMap::new();
+    /// values.insert("use_colors".to_owned(), "off".to_owned());
+    ///
+    /// let changed = section
+    ///     .set_options(&values)
+    ///     .values()
+    ///     .filter(|result| matches!(result, OptionChanged::Changed))
+    ///     .count();
+    ///
+    /// Weechat::print(&format!("{} options changed", changed));
+    /// ```
+    pub fn set_options(&self, values: &HashMap<String, String>) -> HashMap<String, OptionChanged> {
+        values
+            .iter()
+            .map(|(name, value)| {
+                let result = match self.search_option(name) {
+                    Some(option) => option.set_value(value),
+                    None => OptionChanged::NotFound,
+                };
+
+                (name.clone(), result)
+            })
+            .collect()
+    }
+
     /// Create a new string Weechat configuration option.
     ///
     /// Returns None if the option couldn't be created, e.g. if a option with
@@ -526,6 +674,72 @@ impl ConfigSection {
         Ok(option)
     }
 
+    /// Create a new string Weechat configuration option that references
+    /// Weechat secured data.
+    ///
+    /// Returns None if the option couldn't be created, e.g. if a option with
+    /// the same name already exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - Settings that decide how the option should be created.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use weechat::config::{Config, ConfigSectionSettings, SecureStringOptionSettings};
+    /// # let mut config = Config::new("my_plugin").unwrap();
+    /// # let mut section = config.new_section(ConfigSectionSettings::new("network")).unwrap();
+    /// let settings = SecureStringOptionSettings::new("api_token")
+    ///     .description("API token (note: content is evaluated, see /help secure)");
+    ///
+    /// let option = section
+    ///     .new_secure_string_option(settings)
+    ///     .expect("Can't create option");
+    ///
+    /// let token = option.evaluated_value();
+    /// ```
+    pub fn new_secure_string_option(
+        &mut self,
+        settings: SecureStringOptionSettings,
+    ) -> Result<StringOption, ()> {
+        let default_value = settings.default_value();
+        let check_cb: Box<CheckCB<StringOption>> =
+            Box::new(|_: &Weechat, _: &StringOption, value: Cow<str>| {
+                SecureStringOptionSettings::is_secure_reference(&value)
+            });
+
+        let ret = self.new_option(
+            OptionDescription {
+                name: &settings.name,
+                description: &settings.description,
+                option_type: OptionType::String,
+                default_value: &default_value,
+                value: &default_value,
+                ..Default::default()
+            },
+            Some(check_cb),
+            settings.change_cb,
+            None,
+        );
+
+        let (ptr, option_pointers) = if let Some((ptr, ptrs)) = ret {
+            (ptr, ptrs)
+        } else {
+            return Err(());
+        };
+
+        let option_ptrs = ConfigOptionPointers::String(option_pointers);
+        self.option_pointers.insert(settings.name, option_ptrs);
+
+        let option = StringOption {
+            ptr,
+            weechat_ptr: self.weechat_ptr,
+            _phantom: PhantomData,
+        };
+        Ok(option)
+    }
+
     /// Create a new boolean Weechat configuration option.
     ///
     /// Returns None if the option couldn't be created, e.g. if a option with
@@ -616,6 +830,62 @@ impl ConfigSection {
         Ok(option)
     }
 
+    /// Create a new enum Weechat configuration option.
+    ///
+    /// The option is an integer option that takes the names of the enum
+    /// variants as its values, the option will show up as an integer option
+    /// when searched for.
+    ///
+    /// Returns None if the option couldn't be created, e.g. if a option with
+    /// the same name already exists.
+    ///
+    /// # Arguments
+    /// * `settings` - Settings that decide how the option should be created.
+    #[cfg(feature = "config_macro")]
+    #[cfg_attr(feature = "docs", doc(cfg(config_macro)))]
+    pub fn new_enum_option<T>(
+        &mut self,
+        settings: EnumOptionSettings<T>,
+    ) -> Result<EnumOption<T>, ()>
+    where
+        T: strum::VariantNames + From<i32> + PartialEq + 'static,
+    {
+        let default_value = settings.default_variant();
+
+        let change_cb = settings.change_cb.map(|mut callback| {
+            Box::new(move |weechat: &Weechat, option: &IntegerOption| {
+                let option = EnumOption::from_ptrs(option.ptr, option.weechat_ptr);
+                callback(weechat, &option)
+            }) as Box<dyn FnMut(&Weechat, &IntegerOption)>
+        });
+
+        let ret = self.new_option(
+            OptionDescription {
+                name: &settings.name,
+                option_type: OptionType::Integer,
+                description: &settings.description,
+                string_values: &T::VARIANTS.join("|"),
+                default_value,
+                value: default_value,
+                ..Default::default()
+            },
+            None,
+            change_cb,
+            None,
+        );
+
+        let (ptr, option_pointers) = if let Some((ptr, ptrs)) = ret {
+            (ptr, ptrs)
+        } else {
+            return Err(());
+        };
+
+        let option_ptrs = ConfigOptionPointers::Integer(option_pointers);
+        self.option_pointers.insert(settings.name, option_ptrs);
+
+        Ok(EnumOption::from_ptrs(ptr, self.weechat_ptr))
+    }
+
     /// Create a new color Weechat configuration option.
     ///
     /// Returns None if the option couldn't be created, e.g. if a option with
@@ -108,6 +108,106 @@ impl StringOptionSettings {
     }
 }
 
+/// Settings for a new string option that holds a reference to Weechat secured
+/// data.
+///
+/// The option value will be a reference in the form of `${sec.data.name}`, the
+/// secret itself is stored in the `sec.conf` file of Weechat and never ends up
+/// in the configuration file of the plugin. Values that aren't a reference to
+/// secured data are rejected.
+///
+/// The secret can be retrieved using the `StringOption::evaluated_value()`
+/// method. Note that if the secured data is encrypted, Weechat needs the
+/// passphrase to decrypt it, if the passphrase wasn't provided or if the
+/// secured data doesn't exist the evaluated value will be an empty string.
+///
+/// The secured data can be set by the user with the `/secure set name value`
+/// command.
+#[derive(Default)]
+pub struct SecureStringOptionSettings {
+    pub(crate) name: String,
+
+    pub(crate) description: String,
+
+    pub(crate) secure_data_name: Option<String>,
+
+    pub(crate) change_cb: Option<Box<dyn FnMut(&Weechat, &StringOption)>>,
+}
+
+impl SecureStringOptionSettings {
+    /// Create new settings that can be used to create a new secured string
+    /// option.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the new option.
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        SecureStringOptionSettings {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the description of the option.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - The description of the new option.
+    pub fn description<D: Into<String>>(mut self, descritpion: D) -> Self {
+        self.description = descritpion.into();
+        self
+    }
+
+    /// Set the name of the secured data the option should reference by
+    /// default.
+    ///
+    /// Defaults to the name of the option.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the secured data, the default value of the
+    ///     option will be `${sec.data.name}`.
+    pub fn secure_data_name<N: Into<String>>(mut self, name: N) -> Self {
+        self.secure_data_name = Some(name.into());
+        self
+    }
+
+    /// Set the callback that will run when the value of the option changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback that will be run.
+    ///
+    /// # Examples
+    /// ```
+    /// use weechat::Weechat;
+    /// use weechat::config::SecureStringOptionSettings;
+    ///
+    /// let settings = SecureStringOptionSettings::new("access_token")
+    ///     .secure_data_name("matrix_token")
+    ///     .set_change_callback(|weechat, option| {
+    ///         Weechat::print("Option changed");
+    ///     });
+    /// ```
+    pub fn set_change_callback(
+        mut self,
+        callback: impl FnMut(&Weechat, &StringOption) + 'static,
+    ) -> Self {
+        self.change_cb = Some(Box::new(callback));
+        self
+    }
+
+    pub(crate) fn default_value(&self) -> String {
+        let name = self.secure_data_name.as_ref().unwrap_or(&self.name);
+        format!("${{sec.data.{}}}", name)
+    }
+
+    /// Check if the the value is a reference to secured data or empty.
+    pub(crate) fn is_secure_reference(value: &str) -> bool {
+        value.is_empty() || (value.starts_with("${sec.data.") && value.ends_with('}'))
+    }
+}
+
 /// A config option with a string value.
 pub struct StringOption<'a> {
     pub(crate) ptr: *mut t_config_option,
@@ -125,6 +225,18 @@ impl<'a> StringOption<'a> {
             CStr::from_ptr(string).to_string_lossy()
         }
     }
+
+    /// Get the value of the option with Weechat expressions evaluated.
+    ///
+    /// This resolves references to secured data, e.g. `${sec.data.name}`, to
+    /// the secret they point to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn evaluated_value(&self) -> Result<String, ()> {
+        Weechat::eval_string_expression(&self.value())
+    }
 }
 
 impl<'a> FromPtrs for StringOption<'a> {
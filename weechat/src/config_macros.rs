@@ -15,6 +15,12 @@ macro_rules! option_settings {
             .min($min)
             .max($max)
     };
+    (Integer, $option_name:ident, $description:literal, $default:literal, values: [$($value:literal),* $(,)?] $(,)?) => {
+        weechat::config::IntegerOptionSettings::new(stringify!($option_name))
+            .description($description)
+            .default_value($default)
+            .string_values(vec![$($value),*])
+    };
     (Enum, $option_name:ident, $description:literal, $out_type:ty $(,)?) => {
         weechat::config::IntegerOptionSettings::new(stringify!($option_name))
             .description($description)
@@ -84,37 +90,80 @@ macro_rules! option_getter {
     };
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! option_setter {
+    ($name:ident, $string_name:expr, $input_type:ty, $value:ident => $string_value:expr) => {
+        $crate::paste::item! {
+            pub fn [<set_ $name>](&self, $value: $input_type) -> weechat::config::OptionChanged {
+                let option = self.0.search_option($string_name)
+                    .expect(&format!("Couldn't find option {} in section {}",
+                                     $string_name, self.0.name()));
+
+                option.set(&$string_value, true)
+            }
+        }
+    };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! option {
     (String, $name:ident, $description:literal, $($args:tt)*) => {
         $crate::option_create!(String, String, $name, $description, $($args)*);
         $crate::option_getter!(String, $name, stringify!($name), $description, String);
+        $crate::option_setter!($name, stringify!($name), &str, value => value);
     };
 
     (Color, $name:ident, $description:literal, $($args:tt)*) => {
         $crate::option_create!(Color, Color, $name, $description, $($args)*);
         $crate::option_getter!(Color, $name, stringify!($name), $description, String);
+        $crate::option_setter!($name, stringify!($name), &str, value => value);
     };
 
     (bool, $name:ident, $description:literal, $($args:tt)*) => {
         $crate::option_create!(Boolean, Boolean, $name, $description, $($args)*);
         $crate::option_getter!(Boolean, $name, stringify!($name), $description, bool);
+        $crate::option_setter!($name, stringify!($name), bool,
+                               value => if value { "on" } else { "off" });
+    };
+
+    (Integer, $name:ident, $description:literal, $default:literal,
+     values: [$($value:literal),* $(,)?] $(,)?) => {
+        $crate::option_create!(Integer, Integer, $name, $description, $default,
+                               values: [$($value),*]);
+        $crate::option_getter!(Integer, $name, stringify!($name), $description, i64);
+        // Integer options with string values can only be set using one of
+        // the string values.
+        $crate::option_setter!($name, stringify!($name), i64,
+                               value => [$($value),*]
+                                   .get(value as usize)
+                                   .map(|v| v.to_string())
+                                   .unwrap_or_default());
     };
 
     (Integer, $name:ident, $description:literal, $($args:tt)*) => {
         $crate::option_create!(Integer, Integer, $name, $description, $($args)*);
         $crate::option_getter!(Integer, $name, stringify!($name), $description, i64);
+        $crate::option_setter!($name, stringify!($name), i64, value => value.to_string());
     };
 
     (Enum, $name:ident, $description:literal, $out_type:ty $(,)?) => {
         $crate::option_create!(Enum, Integer, $name, $description, $out_type);
         $crate::option_getter!(Integer, $name, stringify!($name), $description, $out_type);
+        // The enum is stored as an integer option using the variant names as
+        // string values, so the option needs to be set using the variant name.
+        $crate::option_setter!($name, stringify!($name), $out_type,
+                               value => <$out_type>::VARIANTS
+                                   .get(value as usize)
+                                   .copied()
+                                   .unwrap_or_default());
     };
 
     (EvaluatedString, $name:ident, $description:literal, $($args:tt)*) => {
         $crate::option_create!(String, String, $name, $description, $($args)*);
         $crate::option_getter!(EvaluatedString, $name, stringify!($name), $description);
+        $crate::option_setter!($name, stringify!($name), &str, value => value);
     };
 }
 
@@ -164,6 +213,16 @@ macro_rules! section {
                     )*
                 }
 
+                /// Reset all the declared options of the section to their
+                /// default values.
+                pub fn reset_to_defaults(&self) {
+                    $(
+                        if let Some(option) = self.0.search_option(stringify!($option_name)) {
+                            option.reset(true);
+                        }
+                    )*
+                }
+
                 $(
                     $crate::option!($option_type, $option_name, $($option)*);
                 )*
@@ -200,6 +259,12 @@ macro_rules! section_getter {
 /// of the same name. The generated struct will have accessors for every
 /// section and option that is declared.
 ///
+/// Every option gets a getter with the name of the option and a setter
+/// prefixed with `set_`, e.g. `config.look().set_encrypted_room_sign("🔒")`.
+/// The setters return the `OptionChanged` status of the change and run the
+/// change callback of the option. Setters for enum options take the enum
+/// variant.
+///
 /// The generated struct dereferences into the Weechat `Config` struct so
 /// additional sections and options can be created the usual way as well.
 ///
@@ -296,6 +361,19 @@ macro_rules! section_getter {
 ///             0..100,
 ///         },
 ///
+///         verbosity: Integer {
+///             // Description.
+///             "How much information should be printed",
+///
+///             // Default value, the index of the default label.
+///             1,
+///
+///             // The labels that are displayed to the user, the option is
+///             // still stored and returned as a number, the index of the
+///             // label.
+///             values: ["low", "mid", "high"],
+///         },
+///
 ///         autoconnect: bool {
 ///             // Description.
 ///             "Automatically connect to the server when Weechat is starting",
@@ -306,6 +384,64 @@ macro_rules! section_getter {
 ///    }
 /// );
 /// ```
+///
+/// The generated setters, `reset_to_defaults()` and `save()` can then be used
+/// once the config is created:
+///
+/// ```no_run
+/// # use weechat::config;
+/// # use strum_macros::EnumVariantNames;
+/// #
+/// # #[derive(EnumVariantNames)]
+/// # #[strum(serialize_all = "kebab_case")]
+/// # pub enum ServerBufferMerge {
+/// #     MergeWithCore,
+/// #     Independent,
+/// # }
+/// #
+/// # impl Default for ServerBufferMerge {
+/// #     fn default() -> Self {
+/// #         ServerBufferMerge::MergeWithCore
+/// #     }
+/// # }
+/// #
+/// # impl From<i32> for ServerBufferMerge {
+/// #     fn from(value: i32) -> Self {
+/// #         match value {
+/// #             0 => ServerBufferMerge::MergeWithCore,
+/// #             _ => ServerBufferMerge::Independent,
+/// #         }
+/// #     }
+/// # }
+/// #
+/// # config!(
+/// #     "my-plugin",
+/// #     Section look {
+/// #         encrypted_room_sign: String { "", "🔒", },
+/// #         server_buffer: Enum { "", ServerBufferMerge, },
+/// #         quote_fg: Color { "", "lightgreen", },
+/// #     },
+/// #     Section network {
+/// #         username: EvaluatedString { "", "", },
+/// #         timeout: Integer { "", 30, 0..100, },
+/// #         verbosity: Integer { "", 1, values: ["low", "mid", "high"], },
+/// #         autoconnect: bool { "", false, },
+/// #     }
+/// # );
+/// let config = Config::new().expect("Can't create the config");
+///
+/// config.look().set_encrypted_room_sign("🔐");
+/// config.look().set_server_buffer(ServerBufferMerge::Independent);
+/// config.look().set_quote_fg("green");
+/// config.network().set_username("${sec.data.username}");
+/// config.network().set_timeout(60);
+/// config.network().set_verbosity(2);
+/// config.network().set_autoconnect(true);
+///
+/// // Go back to the default values and write them to disk.
+/// config.reset_to_defaults();
+/// config.save().expect("Can't write the config");
+/// ```
 #[cfg(feature = "config_macro")]
 #[cfg_attr(feature = "docs", doc(cfg(config_macro)))]
 #[macro_export]
@@ -367,6 +503,19 @@ macro_rules! config {
                 }
             }
 
+            /// Reset all the declared options of the config to their default
+            /// values.
+            pub fn reset_to_defaults(&self) {
+                $(
+                    self.$section().reset_to_defaults();
+                )*
+            }
+
+            /// Write the config to disk.
+            pub fn save(&self) -> std::io::Result<()> {
+                self.0.write()
+            }
+
             $(
                 $crate::section_getter!($section, stringify!($section));
             )*
@@ -3,8 +3,8 @@ use futures::future::{BoxFuture, Future};
 use pipe_channel::{channel, Receiver, Sender};
 use std::{
     collections::VecDeque,
-    panic,
-    sync::{Arc, Mutex},
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex, MutexGuard},
 };
 
 use crate::{
@@ -40,6 +40,20 @@ enum ExecutorJob {
 
 type FutureQueue = Arc<Mutex<VecDeque<ExecutorJob>>>;
 
+/// Lock a mutex of the executor, ignoring lock poisoning.
+///
+/// A future that panics while it's being polled must not take down the
+/// executor with it, the queues are still consistent in that case since
+/// they are only modified while holding the lock.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Run a job, catching a panic so it only takes down the job itself.
+fn run_isolated(job: impl FnOnce() -> bool) {
+    let _ = panic::catch_unwind(AssertUnwindSafe(job));
+}
+
 #[derive(Clone)]
 pub struct WeechatExecutor {
     _hook: Arc<Mutex<Option<FdHook<Receiver<()>>>>>,
@@ -51,39 +65,12 @@ pub struct WeechatExecutor {
 impl FdHookCallback for WeechatExecutor {
     type FdObject = Receiver<()>;
 
-    fn callback(&mut self, _weechat: &Weechat, receiver: &mut Receiver<()>) {
+    fn callback(&mut self, weechat: &Weechat, receiver: &mut Receiver<()>) {
         if receiver.recv().is_err() {
             return;
         }
 
-        let future = self.futures.lock().unwrap().pop_front();
-
-        // Run a local future if there is one.
-        if let Some(task) = future {
-            match task {
-                ExecutorJob::Job(t) => {
-                    let _ = panic::catch_unwind(|| t.run());
-                }
-                ExecutorJob::BufferJob(t) => {
-                    let weechat = unsafe { Weechat::weechat() };
-                    let buffer_name = t.tag();
-
-                    let buffer = weechat.buffer_search("==", buffer_name);
-
-                    if buffer.is_some() {
-                        let _ = panic::catch_unwind(|| t.run());
-                    } else {
-                        t.cancel()
-                    }
-                }
-            }
-        }
-
-        let future = self.non_local_futures.lock().unwrap().pop_front();
-        // Spawn a future if there was one sent from another thread.
-        if let Some(future) = future {
-            self.spawn_local(future).detach();
-        }
+        self.run_next_job(|buffer_name| weechat.buffer_search("==", buffer_name).is_some());
     }
 }
 
@@ -104,11 +91,44 @@ impl WeechatExecutor {
         let hook = FdHook::new(receiver, FdHookMode::Read, executor.clone())
             .expect("Can't create executor FD hook");
 
-        *executor._hook.lock().unwrap() = Some(hook);
+        *lock(&executor._hook) = Some(hook);
 
         executor
     }
 
+    /// Run the next job of the queue, this is called once for every
+    /// notification the executor receives.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer_exists` - Checks if the buffer with the given full name
+    ///     still exists, jobs of buffers that are gone are cancelled.
+    fn run_next_job(&self, buffer_exists: impl Fn(&str) -> bool) {
+        let future = lock(&self.futures).pop_front();
+
+        // Run a local future if there is one. A panicking future gets
+        // reported by our panic hook and dropped, the rest of the futures
+        // will continue to be polled.
+        if let Some(task) = future {
+            match task {
+                ExecutorJob::Job(t) => run_isolated(|| t.run()),
+                ExecutorJob::BufferJob(t) => {
+                    if buffer_exists(t.tag()) {
+                        run_isolated(|| t.run());
+                    } else {
+                        t.cancel()
+                    }
+                }
+            }
+        }
+
+        let future = lock(&self.non_local_futures).pop_front();
+        // Spawn a future if there was one sent from another thread.
+        if let Some(future) = future {
+            self.spawn_local(future).detach();
+        }
+    }
+
     pub fn spawn_local<F>(&self, future: F) -> Task<F::Output>
     where
         F: Future + 'static,
@@ -123,13 +143,8 @@ impl WeechatExecutor {
 
             if let Some(q) = queue {
                 let sender = sender.expect("Futures queue exists but the channel got dropped");
-                let mut weechat_notify = sender
-                    .lock()
-                    .expect("Weechat notification sender lock is poisoned");
-
-                let mut queue = q
-                    .lock()
-                    .expect("Lock of the future queue of the Weechat executor is poisoned");
+                let mut weechat_notify = lock(&sender);
+                let mut queue = lock(&q);
 
                 queue.push_back(ExecutorJob::Job(runnable));
                 weechat_notify
@@ -166,12 +181,9 @@ impl WeechatExecutor {
         let executor = unsafe { _EXECUTOR.as_ref().expect("Executor wasn't started") };
 
         let future = Box::pin(future);
-        let mut queue = executor.non_local_futures.lock().unwrap();
+        let mut queue = lock(&executor.non_local_futures);
         queue.push_back(future);
-        executor
-            .sender
-            .lock()
-            .unwrap()
+        lock(&executor.sender)
             .send(())
             .expect("Can't notify Weechat to spawn a non-local future");
     }
@@ -207,13 +219,8 @@ impl WeechatExecutor {
 
             if let Some(q) = queue {
                 let sender = sender.expect("Futures queue exists but the channel got dropped");
-                let mut weechat_notify = sender
-                    .lock()
-                    .expect("Weechat notification sender lock is poisoned");
-
-                let mut queue = q
-                    .lock()
-                    .expect("Lock of the future queue of the Weechat executor is poisoned");
+                let mut weechat_notify = lock(&sender);
+                let mut queue = lock(&q);
 
                 queue.push_back(ExecutorJob::BufferJob(BufferJob(
                     runnable,
@@ -232,3 +239,135 @@ impl WeechatExecutor {
         task
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::task::noop_waker_ref;
+    use std::{
+        cell::Cell,
+        pin::Pin,
+        rc::Rc,
+        task::{Context, Poll},
+    };
+
+    /// Create an executor that isn't hooked into Weechat, the receiver needs
+    /// to be kept alive so the executor can send its notifications.
+    fn executor() -> (WeechatExecutor, Receiver<()>) {
+        let (sender, receiver) = channel();
+
+        let executor = WeechatExecutor {
+            _hook: Arc::new(Mutex::new(None)),
+            sender: Arc::new(Mutex::new(sender)),
+            futures: Arc::new(Mutex::new(VecDeque::new())),
+            non_local_futures: Arc::new(Mutex::new(VecDeque::new())),
+        };
+
+        (executor, receiver)
+    }
+
+    /// A future that wakes itself up and yields once.
+    struct YieldNow(bool);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn panicking_job_doesnt_stop_the_queue() {
+        let (executor, _receiver) = executor();
+        let ran = Rc::new(Cell::new(false));
+        let healthy_ran = ran.clone();
+
+        executor
+            .spawn_local(async { panic!("Job panicked") })
+            .detach();
+        executor
+            .spawn_local(async move { healthy_ran.set(true) })
+            .detach();
+
+        executor.run_next_job(|_| true);
+        assert!(!ran.get());
+
+        executor.run_next_job(|_| true);
+        assert!(ran.get());
+        assert!(lock(&executor.futures).is_empty());
+    }
+
+    #[test]
+    fn cancelled_task_isnt_polled_again() {
+        let (executor, _receiver) = executor();
+        let polls = Rc::new(Cell::new(0));
+        let future_polls = polls.clone();
+
+        let task = executor.spawn_local(async move {
+            loop {
+                future_polls.set(future_polls.get() + 1);
+                YieldNow(false).await;
+            }
+        });
+
+        executor.run_next_job(|_| true);
+        executor.run_next_job(|_| true);
+        assert_eq!(polls.get(), 2);
+
+        let mut cx = Context::from_waker(noop_waker_ref());
+        let mut cancel = Box::pin(task.cancel());
+
+        // The task is still queued, the cancellation finishes once the
+        // executor gets to it.
+        assert!(cancel.as_mut().poll(&mut cx).is_pending());
+
+        executor.run_next_job(|_| true);
+        assert_eq!(cancel.as_mut().poll(&mut cx), Poll::Ready(None));
+
+        executor.run_next_job(|_| true);
+        assert_eq!(polls.get(), 2);
+        assert!(lock(&executor.futures).is_empty());
+    }
+
+    #[test]
+    fn jobs_of_closed_buffers_are_cancelled() {
+        let (executor, _receiver) = executor();
+        let ran = Rc::new(Cell::new(false));
+        let future_ran = ran.clone();
+
+        let (runnable, task) = async_task::spawn_local(async move { future_ran.set(true) }, |_| ());
+        task.detach();
+
+        lock(&executor.futures).push_back(ExecutorJob::BufferJob(BufferJob(
+            runnable,
+            "core.closed".to_owned(),
+        )));
+
+        executor.run_next_job(|buffer_name| buffer_name != "core.closed");
+
+        assert!(!ran.get());
+        assert!(lock(&executor.futures).is_empty());
+    }
+
+    #[test]
+    fn lock_ignores_poisoning() {
+        let queue = Arc::new(Mutex::new(VecDeque::from(vec![1])));
+        let poisoner = queue.clone();
+
+        run_isolated(|| {
+            let _guard = poisoner.lock().unwrap();
+            panic!("Panicked while holding the lock");
+        });
+
+        assert!(queue.is_poisoned());
+        lock(&queue).push_back(2);
+        assert_eq!(*lock(&queue), VecDeque::from(vec![1, 2]));
+    }
+}
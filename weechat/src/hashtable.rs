@@ -1,4 +1,8 @@
-use std::{collections::HashMap, ffi::c_void, os::raw::c_char};
+use std::{
+    collections::HashMap,
+    ffi::{c_void, CStr},
+    os::raw::c_char,
+};
 
 use weechat_sys::{t_hashtable, WEECHAT_HASHTABLE_STRING};
 
@@ -27,4 +31,74 @@ impl Weechat {
 
         hashtable
     }
+
+    /// Insert the entries of a string hashmap into an existing Weechat hashtable.
+    pub(crate) fn hashtable_set_all(
+        &self,
+        hashtable: *mut t_hashtable,
+        hashmap: &HashMap<String, String>,
+    ) {
+        let hashtable_set = self.get().hashtable_set.unwrap();
+
+        for (key, value) in hashmap {
+            let key = LossyCString::new(key.as_str());
+            let value = LossyCString::new(value.as_str());
+
+            unsafe {
+                hashtable_set(
+                    hashtable,
+                    key.as_ptr() as *const c_void,
+                    value.as_ptr() as *const c_void,
+                );
+            }
+        }
+    }
+
+    /// Convert a Weechat hashtable into a string hashmap.
+    ///
+    /// Keys and values of other types are converted to strings by Weechat.
+    pub(crate) fn hashtable_to_hashmap(
+        &self,
+        hashtable: *mut t_hashtable,
+    ) -> HashMap<String, String> {
+        unsafe extern "C" fn map_cb(
+            data: *mut c_void,
+            _hashtable: *mut t_hashtable,
+            key: *const c_char,
+            value: *const c_char,
+        ) {
+            let hashmap = &mut *(data as *mut HashMap<String, String>);
+
+            if key.is_null() {
+                return;
+            }
+
+            let key = CStr::from_ptr(key).to_string_lossy().into_owned();
+            let value = if value.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(value).to_string_lossy().into_owned()
+            };
+
+            hashmap.insert(key, value);
+        }
+
+        let mut hashmap = HashMap::new();
+
+        if hashtable.is_null() {
+            return hashmap;
+        }
+
+        let hashtable_map_string = self.get().hashtable_map_string.unwrap();
+
+        unsafe {
+            hashtable_map_string(
+                hashtable,
+                Some(map_cb),
+                &mut hashmap as *mut _ as *mut c_void,
+            )
+        };
+
+        hashmap
+    }
 }
@@ -1,10 +1,55 @@
+//! Read-only access to Weechat's internal data structures.
+//!
+//! Hdata describes the structures Weechat and its plugins use internally,
+//! e.g. buffers, windows or IRC servers, and allows reading their fields by
+//! name. This gives access to a lot of information that isn't exposed
+//! otherwise, the list of available hdata and their variables can be found
+//! in the Weechat plugin API reference.
+//!
+//! # Safety
+//!
+//! An `HdataItem` wraps a pointer to a Weechat structure. Weechat frees those
+//! structures at any time it gets control back, e.g. once the current
+//! callback returns or when a command is run, and the item can't tell if
+//! that happened. Reading an item or moving to another one is therefore
+//! `unsafe`, the caller needs to make sure that the structure is still
+//! alive. Items should be fetched, read and dropped inside of a single
+//! callback without running Weechat operations that could free the
+//! structure in between.
+//!
+//! `Hdata::list()` returns an item that is valid at the time it is returned,
+//! items returned by the methods of a valid item are valid as well.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use weechat::Weechat;
+//! # let weechat = unsafe { Weechat::weechat() };
+//! let hdata = weechat.hdata("irc_server").expect("The irc plugin isn't loaded");
+//!
+//! let mut server = hdata.list("irc_servers");
+//!
+//! while let Some(item) = server {
+//!     // The servers can't be freed while we're walking the list, we don't
+//!     // give control back to Weechat.
+//!     unsafe {
+//!         let name = item.get_string("name").unwrap_or_default();
+//!         let connected = item.get_integer("is_connected") != 0;
+//!
+//!         Weechat::print(&format!("{} connected: {}", name, connected));
+//!
+//!         server = item.next();
+//!     }
+//! }
+//! ```
+
 use std::{
     borrow::Cow,
     collections::HashMap,
     ffi::{c_void, CStr},
     os::raw::c_char,
 };
-use weechat_sys::t_hdata;
+use weechat_sys::{t_hashtable, t_hdata};
 
 use crate::{LossyCString, Weechat};
 
@@ -17,6 +62,14 @@ impl Weechat {
         hdata_get(self.ptr, name.as_ptr())
     }
 
+    pub(crate) unsafe fn hdata_get_list(&self, hdata: *mut t_hdata, name: &str) -> *mut c_void {
+        let hdata_get_list = self.get().hdata_get_list.unwrap();
+
+        let name = LossyCString::new(name);
+
+        hdata_get_list(hdata, name.as_ptr())
+    }
+
     pub(crate) unsafe fn hdata_pointer(
         &self,
         hdata: *mut t_hdata,
@@ -65,6 +118,18 @@ impl Weechat {
         hdata_char(hdata, pointer, name.as_ptr())
     }
 
+    pub(crate) unsafe fn hdata_hashtable(
+        &self,
+        hdata: *mut t_hdata,
+        pointer: *mut c_void,
+        name: &str,
+    ) -> *mut t_hashtable {
+        let hdata_hashtable = self.get().hdata_hashtable.unwrap();
+        let name = LossyCString::new(name);
+
+        hdata_hashtable(hdata, pointer, name.as_ptr())
+    }
+
     pub(crate) unsafe fn hdata_var_array_size(
         &self,
         hdata: *mut t_hdata,
@@ -87,6 +152,16 @@ impl Weechat {
         hdata_move(hdata, pointer, offset)
     }
 
+    pub(crate) unsafe fn hdata_check_pointer(
+        &self,
+        hdata: *mut t_hdata,
+        list: *mut c_void,
+        pointer: *mut c_void,
+    ) -> bool {
+        let hdata_check_pointer = self.get().hdata_check_pointer.unwrap();
+        hdata_check_pointer(hdata, list, pointer) != 0
+    }
+
     pub(crate) unsafe fn hdata_string(
         &self,
         hdata: *mut t_hdata,
@@ -114,3 +189,298 @@ impl Weechat {
         ret
     }
 }
+
+impl Weechat {
+    /// Get the hdata with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the hdata, e.g. `buffer` or `irc_server`.
+    ///
+    /// Returns `None` if no hdata with the given name exists, e.g. if the
+    /// plugin that provides it isn't loaded.
+    pub fn hdata(&self, name: &str) -> Option<Hdata> {
+        let ptr = unsafe { self.hdata_get(name) };
+
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Hdata { weechat: self, ptr })
+        }
+    }
+}
+
+/// The hdata of a Weechat structure.
+pub struct Hdata<'a> {
+    weechat: &'a Weechat,
+    ptr: *mut t_hdata,
+}
+
+impl<'a> Hdata<'a> {
+    fn item(&self, ptr: *mut c_void) -> Option<HdataItem<'a>> {
+        if ptr.is_null() {
+            None
+        } else {
+            Some(HdataItem {
+                weechat: self.weechat,
+                hdata: self.ptr,
+                ptr,
+            })
+        }
+    }
+
+    /// Get the head of a list of the hdata.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the list, e.g. `gui_buffers` for the first
+    ///     buffer or `last_gui_buffer` for the last one.
+    ///
+    /// Returns `None` if the list doesn't exist or if it's empty.
+    pub fn list(&self, name: &str) -> Option<HdataItem<'a>> {
+        let ptr = unsafe { self.weechat.hdata_get_list(self.ptr, name) };
+        self.item(ptr)
+    }
+
+    /// Get the name of the hdata a pointer variable points to.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    ///
+    /// Returns `None` if the variable doesn't exist or if it doesn't point to
+    /// a structure with hdata.
+    pub fn variable_hdata(&self, name: &str) -> Option<Cow<str>> {
+        let get_var_hdata = self.weechat.get().hdata_get_var_hdata.unwrap();
+        let name = LossyCString::new(name);
+
+        unsafe {
+            let hdata_name = get_var_hdata(self.ptr, name.as_ptr());
+
+            if hdata_name.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(hdata_name).to_string_lossy())
+            }
+        }
+    }
+}
+
+/// A Weechat structure that is described by hdata.
+///
+/// See the module documentation for the safety contract of items.
+pub struct HdataItem<'a> {
+    weechat: &'a Weechat,
+    hdata: *mut t_hdata,
+    ptr: *mut c_void,
+}
+
+impl<'a> HdataItem<'a> {
+    fn hdata(&self) -> Hdata<'a> {
+        Hdata {
+            weechat: self.weechat,
+            ptr: self.hdata,
+        }
+    }
+
+    /// Get the value of a string variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    ///
+    /// Returns `None` if the variable doesn't exist or if the string is null.
+    ///
+    /// # Safety
+    ///
+    /// The structure of the item must not have been freed, see the module
+    /// documentation.
+    pub unsafe fn get_string(&self, name: &str) -> Option<Cow<'_, str>> {
+        let hdata_string = self.weechat.get().hdata_string.unwrap();
+        let name = LossyCString::new(name);
+
+        let string = hdata_string(self.hdata, self.ptr, name.as_ptr());
+
+        if string.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(string).to_string_lossy())
+        }
+    }
+
+    /// Get the value of an integer variable.
+    ///
+    /// Returns 0 if the variable doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    ///
+    /// # Safety
+    ///
+    /// The structure of the item must not have been freed, see the module
+    /// documentation.
+    pub unsafe fn get_integer(&self, name: &str) -> i32 {
+        self.weechat.hdata_integer(self.hdata, self.ptr, name)
+    }
+
+    /// Get the value of a long variable.
+    ///
+    /// Returns 0 if the variable doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    ///
+    /// # Safety
+    ///
+    /// The structure of the item must not have been freed, see the module
+    /// documentation.
+    pub unsafe fn get_long(&self, name: &str) -> i64 {
+        let hdata_long = self.weechat.get().hdata_long.unwrap();
+        let name = LossyCString::new(name);
+
+        hdata_long(self.hdata, self.ptr, name.as_ptr()) as i64
+    }
+
+    /// Get the value of a time variable as a unix timestamp.
+    ///
+    /// Returns 0 if the variable doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    ///
+    /// # Safety
+    ///
+    /// The structure of the item must not have been freed, see the module
+    /// documentation.
+    pub unsafe fn get_time(&self, name: &str) -> i64 {
+        self.weechat.hdata_time(self.hdata, self.ptr, name) as i64
+    }
+
+    /// Get the value of a pointer variable.
+    ///
+    /// The type of the pointed to structure depends on the variable and the
+    /// pointer is only valid under the same conditions as the item itself.
+    /// Prefer `get_item()` for pointers to structures with hdata.
+    ///
+    /// Returns a null pointer if the variable doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    ///
+    /// # Safety
+    ///
+    /// The structure of the item must not have been freed, see the module
+    /// documentation.
+    pub unsafe fn get_pointer(&self, name: &str) -> *mut c_void {
+        self.weechat.hdata_pointer(self.hdata, self.ptr, name)
+    }
+
+    /// Follow a pointer variable to the structure it points to.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable, e.g. `buffer` for the buffer of
+    ///     an `irc_server`.
+    ///
+    /// Returns `None` if the variable doesn't exist, if it doesn't point to a
+    /// structure with hdata or if the pointer is null.
+    ///
+    /// # Safety
+    ///
+    /// The structure of the item must not have been freed, see the module
+    /// documentation.
+    pub unsafe fn get_item(&self, name: &str) -> Option<HdataItem<'a>> {
+        let hdata = self.hdata();
+        let hdata_name = hdata.variable_hdata(name)?;
+        let target = self.weechat.hdata(&hdata_name)?;
+
+        target.item(self.get_pointer(name))
+    }
+
+    /// Move to another item of the list this item belongs to.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of items to move, negative numbers move
+    ///     backwards.
+    ///
+    /// Returns `None` if the list doesn't contain enough items.
+    ///
+    /// # Safety
+    ///
+    /// The structure of the item must not have been freed, see the module
+    /// documentation.
+    pub unsafe fn move_by(&self, count: i32) -> Option<HdataItem<'a>> {
+        let ptr = self.weechat.hdata_move(self.hdata, self.ptr, count);
+        self.hdata().item(ptr)
+    }
+
+    /// Move to the next item of the list.
+    ///
+    /// # Safety
+    ///
+    /// The structure of the item must not have been freed, see the module
+    /// documentation.
+    pub unsafe fn next(&self) -> Option<HdataItem<'a>> {
+        self.move_by(1)
+    }
+
+    /// Move to the previous item of the list.
+    ///
+    /// # Safety
+    ///
+    /// The structure of the item must not have been freed, see the module
+    /// documentation.
+    pub unsafe fn prev(&self) -> Option<HdataItem<'a>> {
+        self.move_by(-1)
+    }
+
+    /// Move to the first item of the list this item belongs to.
+    ///
+    /// # Safety
+    ///
+    /// The structure of the item must not have been freed, see the module
+    /// documentation.
+    pub unsafe fn first(&self) -> HdataItem<'a> {
+        self.walk(-1)
+    }
+
+    /// Move to the last item of the list this item belongs to.
+    ///
+    /// # Safety
+    ///
+    /// The structure of the item must not have been freed, see the module
+    /// documentation.
+    pub unsafe fn last(&self) -> HdataItem<'a> {
+        self.walk(1)
+    }
+
+    unsafe fn walk(&self, step: i32) -> HdataItem<'a> {
+        let mut current = self.ptr;
+
+        loop {
+            let next = self.weechat.hdata_move(self.hdata, current, step);
+
+            if next.is_null() {
+                break;
+            }
+
+            current = next;
+        }
+
+        HdataItem {
+            weechat: self.weechat,
+            hdata: self.hdata,
+            ptr: current,
+        }
+    }
+
+    /// Get the raw pointer of the structure.
+    pub fn as_ptr(&self) -> *mut c_void {
+        self.ptr
+    }
+}
@@ -1,10 +1,13 @@
 //! Bar items are used to display status information in Weechat.
 use core::ptr;
 use libc::c_char;
-use std::os::raw::c_void;
+use std::{collections::HashMap, os::raw::c_void};
 use weechat_sys::{t_gui_bar_item, t_gui_buffer, t_gui_window, t_hashtable, t_weechat_plugin};
 
-use crate::{buffer::Buffer, LossyCString, Weechat};
+use crate::{
+    buffer::{Buffer, Window},
+    LossyCString, Weechat,
+};
 
 /// Trait for the bar item callback
 ///
@@ -30,8 +33,59 @@ impl<T: FnMut(&Weechat, &Buffer) -> String + 'static> BarItemCallback for T {
     }
 }
 
+/// Trait for the bar item callback that receives the window and the extra
+/// info of the bar item.
+///
+/// A blanket implementation for pure `FnMut` functions exists, if data needs to
+/// be passed to the callback implement this over your struct.
+pub trait BarItemWindowCallback: 'static {
+    /// The callback that should be called after the bar items
+    /// is marked to be updated.
+    ///
+    /// Should return a string that will be displayed by the bar item.
+    ///
+    /// # Arguments
+    ///
+    /// * `weeechat` - A reference to the weechat context.
+    ///
+    /// * `buffer` - The buffer of the window the bar item is drawn in, or the
+    ///     currently visible buffer for root bars.
+    ///
+    /// * `window` - The window the bar item is drawn in, `None` for bars of
+    ///     type root.
+    ///
+    /// * `extra_info` - Extra info Weechat passes to the bar item, e.g. the
+    ///     focus info when the item is drawn in cursor mode.
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        buffer: &Buffer,
+        window: Option<Window>,
+        extra_info: HashMap<String, String>,
+    ) -> String;
+}
+
+impl<T: FnMut(&Weechat, &Buffer, Option<Window>, HashMap<String, String>) -> String + 'static>
+    BarItemWindowCallback for T
+{
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        buffer: &Buffer,
+        window: Option<Window>,
+        extra_info: HashMap<String, String>,
+    ) -> String {
+        self(weechat, buffer, window, extra_info)
+    }
+}
+
+enum BarItemCb {
+    Simple(Box<dyn BarItemCallback>),
+    Window(Box<dyn BarItemWindowCallback>),
+}
+
 struct BarItemCbData {
-    callback: Box<dyn BarItemCallback>,
+    callback: BarItemCb,
     weechat_ptr: *mut t_weechat_plugin,
 }
 
@@ -77,28 +131,78 @@ impl BarItem {
     /// });
     /// ```
     ///
-    // TODO: Provide window object, the callback should accept a Window object
-    // wrapping a t_gui_window
-    //
     // TODO: If we're going to allow bar items to be searched for like we do for
     // buffers, we need to do something about the multiple ownership that may
     // come from this.
     pub fn new(name: &str, callback: impl BarItemCallback) -> Result<BarItem, ()> {
+        BarItem::new_helper(name, BarItemCb::Simple(Box::new(callback)))
+    }
+
+    /// Create a new bar item whose callback receives the window the item is
+    /// drawn in and the extra info of the item.
+    ///
+    /// This is useful for bar items that display different content depending
+    /// on the window they are drawn in, e.g. in window bars.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the new bar item.
+    ///
+    /// * `callback` - The callback that should be called after the bar items
+    /// is marked to be updated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use weechat::Weechat;
+    /// # use weechat::buffer::{Buffer, Window};
+    /// # use weechat::hooks::BarItem;
+    /// let item = BarItem::new_with_window(
+    ///     "window_number",
+    ///     |_: &Weechat, _: &Buffer, window: Option<Window>, _: HashMap<String, String>| {
+    ///         window
+    ///             .map(|w| w.number().to_string())
+    ///             .unwrap_or_default()
+    ///     },
+    /// );
+    /// ```
+    pub fn new_with_window(
+        name: &str,
+        callback: impl BarItemWindowCallback,
+    ) -> Result<BarItem, ()> {
+        BarItem::new_helper(name, BarItemCb::Window(Box::new(callback)))
+    }
+
+    fn new_helper(name: &str, callback: BarItemCb) -> Result<BarItem, ()> {
         unsafe extern "C" fn c_item_cb(
             pointer: *const c_void,
             _data: *mut c_void,
             _bar_item: *mut t_gui_bar_item,
-            _window: *mut t_gui_window,
+            window: *mut t_gui_window,
             buffer: *mut t_gui_buffer,
-            _extra_info: *mut t_hashtable,
+            extra_info: *mut t_hashtable,
         ) -> *mut c_char {
             let data: &mut BarItemCbData = { &mut *(pointer as *mut BarItemCbData) };
             let weechat = Weechat::from_ptr(data.weechat_ptr);
             let buffer = weechat.buffer_from_ptr(buffer);
 
-            let cb_trait = &mut data.callback;
+            let ret = match &mut data.callback {
+                BarItemCb::Simple(cb) => cb.callback(&weechat, &buffer),
+                BarItemCb::Window(cb) => {
+                    let window = if window.is_null() {
+                        None
+                    } else {
+                        Some(weechat.window_from_ptr(window))
+                    };
+                    let extra_info = weechat.hashtable_to_hashmap(extra_info);
 
-            let ret = cb_trait.callback(&weechat, &buffer);
+                    cb.callback(&weechat, &buffer, window, extra_info)
+                }
+            };
 
             // Weechat wants a malloc'ed string
             libc::strdup(LossyCString::new(ret).as_ptr())
@@ -107,7 +211,7 @@ impl BarItem {
         let weechat = unsafe { Weechat::weechat() };
 
         let data = Box::new(BarItemCbData {
-            callback: Box::new(callback),
+            callback,
             weechat_ptr: weechat.ptr,
         });
 
@@ -140,7 +244,15 @@ impl BarItem {
         })
     }
 
+    /// Get the name of the bar item.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Update the content of the bar item, by calling its build callback.
+    ///
+    /// This uses the name the bar item was registered with, so it doesn't
+    /// need to be repeated like for `Weechat::bar_item_update()`.
     pub fn update(&self) {
         Weechat::bar_item_update(&self.name);
     }
@@ -1,16 +1,19 @@
 use libc::{c_char, c_int};
 use std::{borrow::Cow, ffi::CStr, os::raw::c_void, ptr};
 
-use weechat_sys::{t_gui_buffer, t_weechat_plugin, WEECHAT_RC_OK};
+use weechat_sys::{t_gui_buffer, t_hook, t_weechat_plugin, WEECHAT_RC_OK};
 
 use crate::{buffer::Buffer, Args, LossyCString, ReturnCode, Weechat};
 
 use super::Hook;
 
+#[cfg(feature = "async")]
+use futures::future::LocalBoxFuture;
+
 /// Hook for a weechat command, the command is removed when the object is
 /// dropped.
 pub struct Command {
-    _hook: Hook,
+    hook: Hook,
     _hook_data: Box<CommandHookData>,
 }
 
@@ -38,11 +41,55 @@ impl<T: FnMut(&Weechat, &Buffer, Args) + 'static> CommandCallback for T {
     }
 }
 
+/// Trait for the async command callback.
+///
+/// A blanket implementation for pure `FnMut` functions exists, if data needs to
+/// be passed to the callback implement this over your struct.
+#[cfg(feature = "async")]
+#[cfg_attr(feature = "docs", doc(cfg(r#async)))]
+pub trait CommandCallbackAsync: 'static {
+    /// Callback that will be called when the command is executed.
+    ///
+    /// The returned future is spawned on the Weechat executor.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer_name` - The full name of the buffer that received the
+    ///     command, can be used to search for the buffer once the future
+    ///     continues.
+    ///
+    /// * `arguments` - The arguments that were passed to the command, this
+    ///     will include the command as the first argument.
+    fn callback(
+        &mut self,
+        buffer_name: String,
+        arguments: Vec<String>,
+    ) -> LocalBoxFuture<'static, ()>;
+}
+
+#[cfg(feature = "async")]
+impl<T: FnMut(String, Vec<String>) -> LocalBoxFuture<'static, ()> + 'static> CommandCallbackAsync
+    for T
+{
+    fn callback(
+        &mut self,
+        buffer_name: String,
+        arguments: Vec<String>,
+    ) -> LocalBoxFuture<'static, ()> {
+        self(buffer_name, arguments)
+    }
+}
+
 #[derive(Default)]
 /// Description for a new Weechat command that should be created.
 ///
 /// The fields of this struct accept the same string formats that are described
 /// in the Weechat API documentation.
+///
+/// There is no setting to keep the spaces of the command arguments. The
+/// `hook_command()` function of the plugin API version this crate binds
+/// (20200621) has no such flag. The original spacing can still be read from
+/// the `argv_eol` based methods of `Args`, e.g. `Args::split_quoted()`.
 pub struct CommandSettings {
     /// Name of the command.
     name: String,
@@ -84,7 +131,9 @@ impl CommandSettings {
     /// Add an argument to the command.
     ///
     /// Multiple arguments can be added to a command. See the `Command`
-    /// documentation for an example of this.
+    /// documentation for an example of this. Every argument is displayed on
+    /// its own line in the `/help` output, use one argument for every verb of
+    /// the command.
     ///
     /// # Arguments
     ///
@@ -107,9 +156,20 @@ impl CommandSettings {
 
     /// Add a completion definition to the command.
     ///
-    /// Multiple arguments can be added to a command. See the `Command`
+    /// Multiple completions can be added to a command. See the `Command`
     /// documentation for an example of this.
     ///
+    /// Every completion is a space separated list of templates, one template
+    /// for each argument position. Weechat tries the completions in the
+    /// order they were added, the completion whose first words match the
+    /// already typed arguments is used, this allows every verb of a command
+    /// to have its own completion, e.g. `add %(nicks)` and `del %(nicks)`.
+    ///
+    /// Templates of the form `%(name)` refer to completion items, besides
+    /// the ones Weechat and other plugins provide, items registered with a
+    /// `CompletionHook` can be referenced using the name they were hooked
+    /// with.
+    ///
     /// # Arguments
     ///
     /// * `completion` - The completion that should be added to the command.
@@ -117,6 +177,44 @@ impl CommandSettings {
         self.completion.push(completion.into());
         self
     }
+
+    /// Add a completion definition for a verb of the command.
+    ///
+    /// This is a shorthand for `add_completion()` with a completion that
+    /// starts with the verb, the verb itself is completed as well.
+    ///
+    /// Weechat doesn't link completions to arguments. The arguments are only
+    /// displayed in `/help`, the completions are matched against the typed
+    /// words. For the two to agree, add one completion for every verb that
+    /// an `add_argument()` entry lists, in the same order. An argument that
+    /// lists multiple verbs, e.g. `add|del <nick>`, needs one completion per
+    /// verb.
+    ///
+    /// # Arguments
+    ///
+    /// * `verb` - The verb, the first argument of the command, this completion
+    ///     is used for.
+    ///
+    /// * `completion` - The completion for the arguments following the verb.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use weechat::hooks::CommandSettings;
+    /// let settings = CommandSettings::new("friends")
+    ///     .add_argument("add|del <nick>")
+    ///     .add_argument("list")
+    ///     .add_completion_for_verb("add", "%(nicks)")
+    ///     .add_completion_for_verb("del", "%(my_plugin_friends)")
+    ///     .add_completion_for_verb("list", "");
+    /// ```
+    pub fn add_completion_for_verb<V: AsRef<str>, T: AsRef<str>>(
+        self,
+        verb: V,
+        completion: T,
+    ) -> Self {
+        let completion = format!("{} {}", verb.as_ref(), completion.as_ref());
+        self.add_completion(completion.trim_end())
+    }
 }
 
 struct CommandHookData {
@@ -157,6 +255,48 @@ impl<T: FnMut(&Weechat, &Buffer, Cow<str>) -> ReturnCode + 'static> CommandRunCa
     }
 }
 
+/// Settings for a new command-run hook.
+#[derive(Default)]
+pub struct CommandRunSettings {
+    command: String,
+    priority: Option<u32>,
+}
+
+impl CommandRunSettings {
+    /// Create new command-run settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command to override, the wildcard `*` is allowed,
+    ///     e.g. `/input *` hooks all the `/input` subcommands.
+    pub fn new<C: Into<String>>(command: C) -> Self {
+        CommandRunSettings {
+            command: command.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the priority of the hook.
+    ///
+    /// Hooks with a higher priority are called first, hooks without a set
+    /// priority have a priority of 1000.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - The priority of the hook.
+    pub fn priority(mut self, priority: u32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    fn to_hook_string(&self) -> String {
+        match self.priority {
+            Some(priority) => format!("{}|{}", priority, self.command),
+            None => self.command.clone(),
+        }
+    }
+}
+
 struct CommandRunHookData {
     callback: Box<dyn CommandRunCallback>,
     weechat_ptr: *mut t_weechat_plugin,
@@ -165,9 +305,18 @@ struct CommandRunHookData {
 impl CommandRun {
     /// Override an existing Weechat command.
     ///
+    /// The command can't be modified by the callback. To rewrite a command,
+    /// eat the original command by returning `ReturnCode::OkEat` and run the
+    /// rewritten command using `Buffer::run_command()`. Take care that the
+    /// rewritten command doesn't match the hook again, otherwise the callback
+    /// will be called recursively.
+    ///
     /// # Arguments
     ///
-    /// * `command` - The command to override (wildcard `*` is allowed).
+    /// * `command` - The command to override (wildcard `*` is allowed), the
+    ///     command may be prefixed with a priority, e.g. `2000|/buffer *`.
+    ///     `CommandRun::with_settings()` can be used instead to set the
+    ///     priority.
     ///
     /// * `callback` - The function that will be called when the command is run.
     ///
@@ -187,8 +336,52 @@ impl CommandRun {
     ///     |_: &Weechat, _: &Buffer, _: Cow<str>| ReturnCode::OkEat,
     /// )
     /// .expect("Can't override buffer command");
+    ///
+    /// // Expand `/q` to a `/quit` command with a message.
+    /// let quit_command = CommandRun::new(
+    ///     "/q",
+    ///     |_: &Weechat, buffer: &Buffer, _: Cow<str>| {
+    ///         buffer.run_command("/quit See you later").ok();
+    ///         ReturnCode::OkEat
+    ///     },
+    /// )
+    /// .expect("Can't override the q command");
     /// ```
     pub fn new(command: &str, callback: impl CommandRunCallback + 'static) -> Result<Self, ()> {
+        CommandRun::with_settings(CommandRunSettings::new(command), callback)
+    }
+
+    /// Override an existing Weechat command using the given settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - The settings of the hook, the command and priority.
+    ///
+    /// * `callback` - The function that will be called when the command is run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::borrow::Cow;
+    /// # use weechat::{Weechat, ReturnCode};
+    /// # use weechat::hooks::{CommandRun, CommandRunSettings};
+    /// # use weechat::buffer::Buffer;
+    ///
+    /// let settings = CommandRunSettings::new("/input *").priority(2000);
+    ///
+    /// let input_command = CommandRun::with_settings(
+    ///     settings,
+    ///     |_: &Weechat, _: &Buffer, _: Cow<str>| ReturnCode::Ok,
+    /// )
+    /// .expect("Can't override input command");
+    /// ```
+    pub fn with_settings(
+        settings: CommandRunSettings,
+        callback: impl CommandRunCallback + 'static,
+    ) -> Result<Self, ()> {
         unsafe extern "C" fn c_hook_cb(
             pointer: *const c_void,
             _data: *mut c_void,
@@ -216,7 +409,7 @@ impl CommandRun {
         let data_ref = Box::leak(data);
         let hook_command_run = weechat.get().hook_command_run.unwrap();
 
-        let command = LossyCString::new(command);
+        let command = LossyCString::new(settings.to_hook_string());
 
         let hook_ptr = unsafe {
             hook_command_run(
@@ -245,6 +438,61 @@ impl CommandRun {
     }
 }
 
+/// A guard that eats a set of commands, the commands are restored when the
+/// guard is dropped.
+///
+/// This is useful to temporarily disable commands, e.g. while the plugin is in
+/// a special input mode.
+///
+/// Guards nest by priority, the command-run hooks of a guard with a higher
+/// priority are called first. Since the commands are eaten, hooks with a lower
+/// priority, including other guards, won't see them until the guard is
+/// dropped. A guard with a lower priority than an existing command-run hook
+/// that eats the command has no effect.
+pub struct CommandGuard {
+    _hooks: Vec<CommandRun>,
+}
+
+impl CommandGuard {
+    /// Eat the given commands until the guard is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `commands` - The commands that should be eaten, the wildcard `*` is
+    ///     allowed, e.g. `/buffer *` eats all the `/buffer` subcommands.
+    ///
+    /// * `priority` - The priority of the command-run hooks, see
+    ///     `CommandRunSettings::priority()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::hooks::CommandGuard;
+    /// let guard = CommandGuard::new(&["/buffer *", "/window *"], 2000)
+    ///     .expect("Can't disable the buffer and window commands");
+    ///
+    /// // The commands work again once the guard is dropped.
+    /// drop(guard);
+    /// ```
+    pub fn new(commands: &[&str], priority: u32) -> Result<Self, ()> {
+        let hooks = commands
+            .iter()
+            .map(|command| {
+                CommandRun::with_settings(
+                    CommandRunSettings::new(*command).priority(priority),
+                    |_: &Weechat, _: &Buffer, _: Cow<str>| ReturnCode::OkEat,
+                )
+            })
+            .collect::<Result<Vec<_>, ()>>()?;
+
+        Ok(CommandGuard { _hooks: hooks })
+    }
+}
+
 impl Command {
     /// Create a new Weechat command.
     ///
@@ -294,43 +542,184 @@ impl Command {
         command_settings: CommandSettings,
         callback: impl CommandCallback + 'static,
     ) -> Result<Command, ()> {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let data = Box::new(CommandHookData {
+            callback: Box::new(callback),
+            weechat_ptr: weechat.ptr,
+        });
+
+        let data_ref = Box::leak(data);
+        let hook_ptr = Command::hook_command(weechat, command_settings, data_ref);
+        let hook_data = unsafe { Box::from_raw(data_ref) };
+
+        let hook = Hook {
+            ptr: hook_ptr,
+            weechat_ptr: weechat.ptr,
+        };
+
+        if hook_ptr.is_null() {
+            Err(())
+        } else {
+            Ok(Command {
+                hook,
+                _hook_data: hook_data,
+            })
+        }
+    }
+
+    /// Create a new Weechat command with an async callback.
+    ///
+    /// Returns the hook of the command. The command is unhooked if the hook is
+    /// dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `command_settings` - Settings for the new command.
+    ///
+    /// * `callback` - The callback that will be called if the command is run,
+    ///     the future the callback returns is spawned on the Weechat executor.
+    ///     If the executor isn't running anymore, e.g. because the plugin is
+    ///     being unloaded, the future is dropped without being polled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// ```no_run
+    /// # use futures::future::{FutureExt, LocalBoxFuture};
+    /// # use weechat::Weechat;
+    /// # use weechat::hooks::{Command, CommandSettings};
+    /// fn fetch(buffer_name: String, args: Vec<String>) -> LocalBoxFuture<'static, ()> {
+    ///     async move {
+    ///         // Do some async work here.
+    ///
+    ///         let weechat = unsafe { Weechat::weechat() };
+    ///
+    ///         if let Some(buffer) = weechat.buffer_search("==", &buffer_name) {
+    ///             buffer.print(&format!("Fetched {:?}", &args[1..]));
+    ///         }
+    ///     }
+    ///     .boxed_local()
+    /// }
+    ///
+    /// let command = Command::new_async(CommandSettings::new("fetch"), fetch)
+    ///     .expect("Can't create command");
+    /// ```
+    #[cfg(feature = "async")]
+    #[cfg_attr(feature = "docs", doc(cfg(r#async)))]
+    pub fn new_async(
+        command_settings: CommandSettings,
+        mut callback: impl CommandCallbackAsync,
+    ) -> Result<Command, ()> {
+        Command::new(
+            command_settings,
+            move |_: &Weechat, buffer: &Buffer, arguments: Args| {
+                let future = callback.callback(buffer.full_name().to_string(), arguments.collect());
+
+                // The executor is gone while the plugin is being unloaded,
+                // the future is dropped without being run in that case.
+                if let Some(task) = Weechat::spawn_checked(future) {
+                    task.detach();
+                }
+            },
+        )
+    }
+
+    /// Replace the settings of the command, e.g. to change its completion
+    /// template.
+    ///
+    /// Weechat doesn't support modifying a command once it's created, the
+    /// command is removed and created again using the new settings, the
+    /// callback of the command is kept.
+    ///
+    /// Returns an error if the command couldn't be created again, the command
+    /// is removed in that case.
+    ///
+    /// # Arguments
+    ///
+    /// * `command_settings` - The new settings for the command.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// ```no_run
+    /// # use weechat::{Weechat, Args};
+    /// # use weechat::hooks::{Command, CommandSettings};
+    /// # use weechat::buffer::{Buffer};
+    /// let settings = CommandSettings::new("sync")
+    ///     .add_argument("start|stop")
+    ///     .add_completion("start|stop");
+    ///
+    /// let mut command = Command::new(
+    ///     settings,
+    ///     |_: &Weechat, buffer: &Buffer, args: Args| {
+    ///         buffer.print(&format!("Command called with args {:?}", args));
+    ///     }
+    /// ).expect("Can't create command");
+    ///
+    /// let settings = CommandSettings::new("sync")
+    ///     .add_argument("start|stop|status")
+    ///     .add_completion("start|stop|status");
+    ///
+    /// command.update(settings).expect("Can't update the command");
+    /// ```
+    pub fn update(&mut self, command_settings: CommandSettings) -> Result<(), ()> {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        // Weechat refuses to create a command with the same name twice, the
+        // old command needs to be removed first.
+        self.hook = Hook {
+            ptr: ptr::null_mut(),
+            weechat_ptr: weechat.ptr,
+        };
+
+        let hook_ptr = Command::hook_command(weechat, command_settings, &*self._hook_data);
+
+        if hook_ptr.is_null() {
+            Err(())
+        } else {
+            self.hook.ptr = hook_ptr;
+            Ok(())
+        }
+    }
+
+    fn hook_command(
+        weechat: &Weechat,
+        command_settings: CommandSettings,
+        hook_data: *const CommandHookData,
+    ) -> *mut t_hook {
         unsafe extern "C" fn c_hook_cb(
             pointer: *const c_void,
             _data: *mut c_void,
             buffer: *mut t_gui_buffer,
             argc: i32,
             argv: *mut *mut c_char,
-            _argv_eol: *mut *mut c_char,
+            argv_eol: *mut *mut c_char,
         ) -> c_int {
             let hook_data: &mut CommandHookData = { &mut *(pointer as *mut CommandHookData) };
             let weechat = Weechat::from_ptr(hook_data.weechat_ptr);
             let buffer = weechat.buffer_from_ptr(buffer);
             let cb = &mut hook_data.callback;
-            let args = Args::new(argc, argv);
+            let args = Args::from_command(argc, argv, argv_eol);
 
             cb.callback(&weechat, &buffer, args);
 
             WEECHAT_RC_OK
         }
 
-        Weechat::check_thread();
-        let weechat = unsafe { Weechat::weechat() };
-
         let name = LossyCString::new(command_settings.name);
         let description = LossyCString::new(command_settings.description);
         let args = LossyCString::new(command_settings.arguments.join("||"));
         let args_description = LossyCString::new(command_settings.argument_descriptoin);
         let completion = LossyCString::new(command_settings.completion.join("||"));
 
-        let data = Box::new(CommandHookData {
-            callback: Box::new(callback),
-            weechat_ptr: weechat.ptr,
-        });
-
-        let data_ref = Box::leak(data);
-
         let hook_command = weechat.get().hook_command.unwrap();
-        let hook_ptr = unsafe {
+
+        unsafe {
             hook_command(
                 weechat.ptr,
                 name.as_ptr(),
@@ -339,24 +728,28 @@ impl Command {
                 args_description.as_ptr(),
                 completion.as_ptr(),
                 Some(c_hook_cb),
-                data_ref as *const _ as *const c_void,
+                hook_data as *const c_void,
                 ptr::null_mut(),
             )
-        };
-        let hook_data = unsafe { Box::from_raw(data_ref) };
+        }
+    }
+}
 
-        let hook = Hook {
-            ptr: hook_ptr,
-            weechat_ptr: weechat.ptr,
-        };
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if hook_ptr.is_null() {
-            Err(())
-        } else {
-            Ok(Command {
-                _hook: hook,
-                _hook_data: hook_data,
-            })
-        }
+    #[test]
+    fn command_run_hook_string_with_priority() {
+        let settings = CommandRunSettings::new("/input *").priority(2000);
+
+        assert_eq!(settings.to_hook_string(), "2000|/input *");
+    }
+
+    #[test]
+    fn command_run_hook_string_without_priority() {
+        let settings = CommandRunSettings::new("/input *");
+
+        assert_eq!(settings.to_hook_string(), "/input *");
     }
 }
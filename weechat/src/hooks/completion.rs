@@ -16,6 +16,7 @@ pub struct Completion {
 /// Trait for the completion callback.
 ///
 /// A blanket implementation for pure `FnMut` functions exists, if data needs to
+/// be passed to the callback implement this over your struct.
 pub trait CompletionCallback {
     /// Callback that will be called if when a completion is requested.
     ///
@@ -90,6 +91,37 @@ impl Completion {
         self.add_with_options(word, false, CompletionPosition::Sorted)
     }
 
+    /// Add a word for completion that is escaped like a shell would do it,
+    /// keeping the list sorted.
+    ///
+    /// Weechat splits command arguments at spaces and doesn't escape
+    /// completion words, a word containing spaces would be inserted as
+    /// multiple arguments. This escapes spaces, quotes and backslashes using
+    /// a backslash, e.g. `My Documents/notes.txt` is inserted as
+    /// `My\ Documents/notes.txt`. The command needs to parse its arguments
+    /// using `Args::split_quoted()` to get the original word back.
+    ///
+    /// Weechat finds the word that is being completed by looking for the
+    /// last space before the cursor, so completing a partially typed word
+    /// that already contains an escaped space only uses the part after the
+    /// space as the base word.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The word that should be escaped and added to the
+    ///     completion.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use weechat::hooks::Completion;
+    /// # fn fill(completion: &Completion) {
+    /// completion.add_escaped("/home/alice/My Documents");
+    /// # }
+    /// ```
+    pub fn add_escaped(&self, word: &str) {
+        self.add(&shell_escape(word))
+    }
+
     /// Get the command used in the completion.
     pub fn base_command(&self) -> Option<Cow<str>> {
         self.get_string("base_command")
@@ -105,6 +137,40 @@ impl Completion {
         self.get_string("args")
     }
 
+    /// Get the position of the argument that is being completed.
+    ///
+    /// The position is zero based and counts the arguments after the
+    /// command, e.g. completing `/my_command add al` returns 1.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::hooks::Completion;
+    /// # fn fill(completion: &Completion) {
+    /// match completion.arg_index() {
+    ///     0 => {
+    ///         completion.add("add");
+    ///         completion.add("del");
+    ///     }
+    ///     _ => completion.add_nick("alice"),
+    /// }
+    /// # }
+    /// ```
+    pub fn arg_index(&self) -> usize {
+        let arguments = self.arguments().unwrap_or_default();
+        let base_word = self.base_word().unwrap_or_default();
+
+        let count = arguments.split_whitespace().count();
+
+        // The arguments contain the word that is being completed, unless
+        // the completion happens after a space.
+        if base_word.is_empty() {
+            count
+        } else {
+            count.saturating_sub(1)
+        }
+    }
+
     fn get_string(&self, property_name: &str) -> Option<Cow<str>> {
         let weechat = Weechat::from_ptr(self.weechat_ptr);
 
@@ -123,9 +189,22 @@ impl Completion {
         }
     }
 
+    /// Add a nick for completion, keeping the list sorted.
+    ///
+    /// This is a shorthand for `add_with_options(nick, true,
+    /// CompletionPosition::Sorted)`.
+    pub fn add_nick(&self, nick: &str) {
+        self.add_with_options(nick, true, CompletionPosition::Sorted)
+    }
+
     /// Add a word to the completion giving the position and wether the word is
     /// a nick.
     ///
+    /// Words that are marked as a nick get the nick completion treatment,
+    /// when completed at the start of the input line the suffix configured in
+    /// the `weechat.completion.nick_completer` option is appended to them.
+    /// Other words, e.g. command keywords, are inserted as is.
+    ///
     /// # Arguments
     ///
     /// * `word` - The word that should be added to the completion.
@@ -133,6 +212,15 @@ impl Completion {
     /// * `is_nick` - Set if the word is a nick.
     ///
     /// * `position` - Set the position where the nick should be added to.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use weechat::hooks::{Completion, CompletionPosition};
+    /// # fn fill(completion: &Completion) {
+    /// completion.add_with_options("alice", true, CompletionPosition::Sorted);
+    /// completion.add_with_options("--force", false, CompletionPosition::End);
+    /// # }
+    /// ```
     pub fn add_with_options(&self, word: &str, is_nick: bool, position: CompletionPosition) {
         let weechat = Weechat::from_ptr(self.weechat_ptr);
 
@@ -160,12 +248,19 @@ struct CompletionHookData {
 }
 
 impl CompletionHook {
-    /// Create a new completion
+    /// Create a new completion item.
+    ///
+    /// The completion item is registered under the given name, commands can
+    /// reference it in their completion templates as `%(name)`, see
+    /// `CommandSettings::add_completion()`. The callback is called every time
+    /// Weechat completes an argument using the template.
+    ///
+    /// Completion item names are global, prefix the name with the name of the
+    /// plugin to avoid collisions.
     ///
     /// # Arguments
     ///
-    /// * `name` - The name of the new completion. After this is created the
-    ///     can be used as `%(name)` when creating commands.
+    /// * `completion_item` - The name of the new completion item.
     ///
     /// * `description` - The description of the new completion.
     ///
@@ -200,7 +295,30 @@ impl CompletionHook {
     ///     "Completion for the list of added Matrix servers",
     ///     servers,
     /// ).unwrap();
+    /// ```
+    ///
+    /// The completion item can then be used by a command:
+    ///
+    /// ```no_run
+    /// # use std::borrow::Cow;
+    /// # use weechat::{Args, Weechat};
+    /// # use weechat::buffer::Buffer;
+    /// # use weechat::hooks::{Command, CommandSettings, Completion, CompletionHook};
+    /// let completion = CompletionHook::new(
+    ///     "my_items",
+    ///     "Completion for my items",
+    ///     |_: &Weechat, _: &Buffer, _: Cow<str>, completion: &Completion| {
+    ///         completion.add("first");
+    ///         completion.add("second");
+    ///         Ok(())
+    ///     },
+    /// ).unwrap();
+    ///
+    /// let settings = CommandSettings::new("my_command")
+    ///     .add_argument("show <item>")
+    ///     .add_completion("show %(my_items)");
     ///
+    /// let command = Command::new(settings, |_: &Weechat, _: &Buffer, _: Args| {});
     /// ```
     pub fn new(
         completion_item: &str,
@@ -277,3 +395,43 @@ impl CompletionHook {
         })
     }
 }
+
+/// Escape spaces, quotes and backslashes using a backslash, the way a shell
+/// would parse them.
+fn shell_escape(word: &str) -> String {
+    let mut escaped = String::with_capacity(word.len());
+
+    for c in word.chars() {
+        if matches!(c, ' ' | '\\' | '\'' | '"') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shell_escape_spaces() {
+        assert_eq!(
+            shell_escape("/home/alice/My Documents"),
+            r"/home/alice/My\ Documents"
+        );
+    }
+
+    #[test]
+    fn shell_escape_quotes_and_backslashes() {
+        assert_eq!(shell_escape(r#"it's "quoted""#), r#"it\'s\ \"quoted\""#);
+        assert_eq!(shell_escape(r"C:\temp"), r"C:\\temp");
+    }
+
+    #[test]
+    fn shell_escape_plain_word() {
+        assert_eq!(shell_escape("notes.txt"), "notes.txt");
+        assert_eq!(shell_escape(""), "");
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    borrow::Cow,
+    ffi::CStr,
+    os::raw::{c_char, c_int, c_void},
+    ptr,
+};
+
+use weechat_sys::{t_weechat_plugin, WEECHAT_RC_OK};
+
+use super::Hook;
+use crate::{LossyCString, Weechat};
+
+/// Hook for config option changes, the hook is removed when the object is
+/// dropped.
+pub struct ConfigHook {
+    _hook: Hook,
+    _hook_data: Box<ConfigHookData>,
+}
+
+struct ConfigHookData {
+    callback: Box<dyn ConfigHookCallback>,
+    weechat_ptr: *mut t_weechat_plugin,
+}
+
+/// Trait for the config hook callback.
+///
+/// A blanket implementation for pure `FnMut` functions exists, if data needs to
+/// be passed to the callback implement this over your struct.
+pub trait ConfigHookCallback {
+    /// Callback that will be called when a config option changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `weechat` - A Weechat context.
+    ///
+    /// * `option_name` - The full name of the option that changed, e.g.
+    ///     `weechat.look.buffer_time_format`.
+    ///
+    /// * `value` - The new value of the option.
+    fn callback(&mut self, weechat: &Weechat, option_name: &str, value: Cow<str>);
+}
+
+impl<T: FnMut(&Weechat, &str, Cow<str>) + 'static> ConfigHookCallback for T {
+    fn callback(&mut self, weechat: &Weechat, option_name: &str, value: Cow<str>) {
+        self(weechat, option_name, value)
+    }
+}
+
+impl ConfigHook {
+    /// Hook config option changes.
+    ///
+    /// This can be used to watch options that are not owned by the plugin,
+    /// options that the plugin created can use a change callback instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `option_mask` - The full name of the option that should be watched,
+    ///     the wildcard `*` is allowed, e.g. `weechat.look.*`.
+    ///
+    /// * `callback` - A function or a struct that implements
+    ///     ConfigHookCallback, the callback method of the trait will be called
+    ///     when a matching option changes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::borrow::Cow;
+    /// # use weechat::Weechat;
+    /// # use weechat::hooks::ConfigHook;
+    /// let config_hook = ConfigHook::new(
+    ///     "weechat.look.*",
+    ///     |_: &Weechat, option_name: &str, value: Cow<str>| {
+    ///         Weechat::print(&format!("{} changed to {}", option_name, value));
+    /// });
+    /// ```
+    pub fn new(option_mask: &str, callback: impl ConfigHookCallback + 'static) -> Result<Self, ()> {
+        unsafe extern "C" fn c_hook_cb(
+            pointer: *const c_void,
+            _data: *mut c_void,
+            option_name: *const c_char,
+            value: *const c_char,
+        ) -> c_int {
+            let hook_data: &mut ConfigHookData = { &mut *(pointer as *mut ConfigHookData) };
+            let cb = &mut hook_data.callback;
+
+            let option_name = CStr::from_ptr(option_name).to_str().unwrap_or_default();
+
+            let value = if value.is_null() {
+                Cow::from("")
+            } else {
+                CStr::from_ptr(value).to_string_lossy()
+            };
+
+            let weechat = Weechat::from_ptr(hook_data.weechat_ptr);
+            cb.callback(&weechat, option_name, value);
+
+            WEECHAT_RC_OK
+        }
+
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let data = Box::new(ConfigHookData {
+            callback: Box::new(callback),
+            weechat_ptr: weechat.ptr,
+        });
+
+        let data_ref = Box::leak(data);
+        let hook_config = weechat.get().hook_config.unwrap();
+
+        let option_mask = LossyCString::new(option_mask);
+
+        let hook_ptr = unsafe {
+            hook_config(
+                weechat.ptr,
+                option_mask.as_ptr(),
+                Some(c_hook_cb),
+                data_ref as *const _ as *const c_void,
+                ptr::null_mut(),
+            )
+        };
+
+        let hook_data = unsafe { Box::from_raw(data_ref) };
+        let hook = Hook {
+            ptr: hook_ptr,
+            weechat_ptr: weechat.ptr,
+        };
+
+        if hook_ptr.is_null() {
+            Err(())
+        } else {
+            Ok(ConfigHook {
+                _hook: hook,
+                _hook_data: hook_data,
+            })
+        }
+    }
+}
@@ -4,12 +4,13 @@ use std::{
     ptr,
 };
 
-use weechat_sys::{t_weechat_plugin, WEECHAT_RC_OK};
+use weechat_sys::{t_hook, t_weechat_plugin, WEECHAT_RC_OK};
 
 use super::Hook;
 use crate::Weechat;
 
 /// Setting for the FdHook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FdHookMode {
     /// Catch read events.
     Read,
@@ -17,29 +18,39 @@ pub enum FdHookMode {
     Write,
     /// Catch read and write events.
     ReadWrite,
+    /// Catch a single read event, the hook is removed after the callback
+    /// runs once.
+    ReadOneShot,
+    /// Catch a single write event, the hook is removed after the callback
+    /// runs once. This is useful to wait for a non-blocking connect to
+    /// finish.
+    WriteOneShot,
 }
 
 impl FdHookMode {
     pub(crate) fn as_tuple(&self) -> (i32, i32) {
         let read = match self {
-            FdHookMode::Read => 1,
+            FdHookMode::Read | FdHookMode::ReadOneShot => 1,
             FdHookMode::ReadWrite => 1,
-            FdHookMode::Write => 0,
+            FdHookMode::Write | FdHookMode::WriteOneShot => 0,
         };
 
         let write = match self {
-            FdHookMode::Read => 0,
+            FdHookMode::Read | FdHookMode::ReadOneShot => 0,
             FdHookMode::ReadWrite => 1,
-            FdHookMode::Write => 1,
+            FdHookMode::Write | FdHookMode::WriteOneShot => 1,
         };
         (read, write)
     }
+
+    fn is_one_shot(&self) -> bool {
+        matches!(self, FdHookMode::ReadOneShot | FdHookMode::WriteOneShot)
+    }
 }
 
 /// Hook for a file descriptor, the hook is removed when the object is dropped.
 pub struct FdHook<F> {
-    _hook: Hook,
-    _hook_data: Box<FdHookData<F>>,
+    hook_data: Box<FdHookData<F>>,
 }
 
 /// Callback trait for file descriptor based hooks.
@@ -56,12 +67,98 @@ pub trait FdHookCallback {
     /// * `fd_object` - The file-descriptor based object that was registered to
     ///     be watched for reads or writes.
     fn callback(&mut self, weechat: &Weechat, fd_object: &mut Self::FdObject);
+
+    /// The callback that will be called with the events that are ready on
+    /// the file descriptor.
+    ///
+    /// Implement this instead of `callback()` if the file descriptor is
+    /// watched for reads and writes and the callback needs to know which
+    /// event happened. The default implementation calls `callback()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `weechat` - A Weechat context.
+    ///
+    /// * `fd_object` - The file-descriptor based object that was registered to
+    ///     be watched for reads or writes.
+    ///
+    /// * `events` - The events that are ready on the file descriptor.
+    fn callback_with_events(
+        &mut self,
+        weechat: &Weechat,
+        fd_object: &mut Self::FdObject,
+        events: FdEvents,
+    ) {
+        let _ = events;
+        self.callback(weechat, fd_object)
+    }
+}
+
+/// The events that are ready on a watched file descriptor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FdEvents {
+    /// Data can be read from the file descriptor.
+    pub readable: bool,
+    /// Data can be written to the file descriptor.
+    pub writable: bool,
+}
+
+impl FdEvents {
+    fn from_mode(fd: c_int, mode: FdHookMode) -> Self {
+        match mode {
+            FdHookMode::Read | FdHookMode::ReadOneShot => FdEvents {
+                readable: true,
+                writable: false,
+            },
+            FdHookMode::Write | FdHookMode::WriteOneShot => FdEvents {
+                readable: false,
+                writable: true,
+            },
+            // Weechat doesn't tell us which of the events happened, ask the
+            // OS without blocking.
+            FdHookMode::ReadWrite => {
+                let mut poll_fd = libc::pollfd {
+                    fd,
+                    events: libc::POLLIN | libc::POLLOUT,
+                    revents: 0,
+                };
+
+                let ret = unsafe { libc::poll(&mut poll_fd, 1, 0) };
+
+                FdEvents::from_poll(ret, poll_fd.revents)
+            }
+        }
+    }
+
+    /// Convert the result of a `poll()` call into a set of events.
+    ///
+    /// If the call failed we can't tell which event happened, both events are
+    /// reported in that case and the callback has to handle a spurious one,
+    /// e.g. a read that would block.
+    fn from_poll(ret: c_int, revents: libc::c_short) -> Self {
+        if ret < 0 {
+            FdEvents {
+                readable: true,
+                writable: true,
+            }
+        } else {
+            FdEvents {
+                readable: revents & (libc::POLLIN | libc::POLLHUP) != 0,
+                writable: revents & libc::POLLOUT != 0,
+            }
+        }
+    }
 }
 
 struct FdHookData<F> {
+    // The hook lives inside of the data so a one-shot callback can remove
+    // it, it's declared first so it's unhooked before the rest of the data
+    // is dropped.
+    hook: Option<Hook>,
     callback: Box<dyn FdHookCallback<FdObject = F>>,
     weechat_ptr: *mut t_weechat_plugin,
     fd_object: F,
+    mode: FdHookMode,
 }
 
 impl<F> FdHook<F> {
@@ -118,40 +215,69 @@ impl<F> FdHook<F> {
         mode: FdHookMode,
         callback: impl FdHookCallback<FdObject = F> + 'static,
     ) -> Result<FdHook<F>, ()>
+    where
+        F: AsRawFd,
+    {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let data = Box::new(FdHookData {
+            hook: None,
+            callback: Box::new(callback),
+            weechat_ptr: weechat.ptr,
+            fd_object,
+            mode,
+        });
+
+        let data_ref = Box::leak(data);
+        let hook_ptr = FdHook::hook_fd(&weechat, data_ref);
+        let mut hook_data = unsafe { Box::from_raw(data_ref) };
+
+        if hook_ptr.is_null() {
+            Err(())
+        } else {
+            hook_data.hook = Some(Hook {
+                ptr: hook_ptr,
+                weechat_ptr: weechat.ptr,
+            });
+
+            Ok(FdHook::<F> { hook_data })
+        }
+    }
+
+    fn hook_fd(weechat: &Weechat, hook_data: *const FdHookData<F>) -> *mut t_hook
     where
         F: AsRawFd,
     {
         unsafe extern "C" fn c_hook_cb<F>(
             pointer: *const c_void,
             _data: *mut c_void,
-            _fd: i32,
+            fd: i32,
         ) -> c_int {
             let hook_data: &mut FdHookData<F> = { &mut *(pointer as *mut FdHookData<F>) };
             let cb = &mut hook_data.callback;
             let mut fd_object = &mut hook_data.fd_object;
             let weechat = Weechat::from_ptr(hook_data.weechat_ptr);
 
-            cb.callback(&weechat, &mut fd_object);
+            let events = FdEvents::from_mode(fd, hook_data.mode);
+            cb.callback_with_events(&weechat, &mut fd_object, events);
+
+            // Weechat allows hooks to be removed while their callback runs.
+            if hook_data.mode.is_one_shot() {
+                hook_data.hook.take();
+            }
 
             WEECHAT_RC_OK
         }
 
-        Weechat::check_thread();
-        let weechat = unsafe { Weechat::weechat() };
-
-        let fd = fd_object.as_raw_fd();
-
-        let data = Box::new(FdHookData {
-            callback: Box::new(callback),
-            weechat_ptr: weechat.ptr,
-            fd_object,
-        });
-
-        let data_ref = Box::leak(data);
         let hook_fd = weechat.get().hook_fd.unwrap();
-        let (read, write) = mode.as_tuple();
 
-        let hook_ptr = unsafe {
+        let (fd, (read, write)) = unsafe {
+            let hook_data = &*hook_data;
+            (hook_data.fd_object.as_raw_fd(), hook_data.mode.as_tuple())
+        };
+
+        unsafe {
             hook_fd(
                 weechat.ptr,
                 fd,
@@ -159,24 +285,183 @@ impl<F> FdHook<F> {
                 write,
                 0,
                 Some(c_hook_cb::<F>),
-                data_ref as *const _ as *const c_void,
+                hook_data as *const c_void,
                 ptr::null_mut(),
             )
-        };
+        }
+    }
 
-        let hook_data = unsafe { Box::from_raw(data_ref) };
-        let hook = Hook {
-            ptr: hook_ptr,
-            weechat_ptr: weechat.ptr,
-        };
+    /// Change the events the file descriptor is watched for.
+    ///
+    /// Weechat doesn't support changing the flags of an existing file
+    /// descriptor hook, the file descriptor is unhooked and hooked again with
+    /// the new mode. The callback and the file descriptor object are kept.
+    ///
+    /// This can be used to implement a client on top of a non-blocking
+    /// socket, the socket is watched for writes while it's connecting and for
+    /// reads afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The new mode the file descriptor should be watched with.
+    ///
+    /// Returns an empty error if the file descriptor couldn't be hooked
+    /// again, the file descriptor isn't watched anymore in that case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// The hook can't be changed from inside of its own callback, the callback
+    /// should record what happened and the mode should be switched afterwards,
+    /// e.g. from a timer.
+    ///
+    /// ```no_run
+    /// # use std::{cell::Cell, net::TcpStream, rc::Rc};
+    /// # use weechat::{Weechat, hooks::{FdEvents, FdHook, FdHookMode, FdHookCallback}};
+    /// struct Client {
+    ///     connected: Rc<Cell<bool>>,
+    /// }
+    ///
+    /// impl FdHookCallback for Client {
+    ///     type FdObject = TcpStream;
+    ///
+    ///     fn callback(&mut self, _: &Weechat, _: &mut TcpStream) {}
+    ///
+    ///     fn callback_with_events(&mut self, _: &Weechat, _: &mut TcpStream, events: FdEvents) {
+    ///         if events.writable {
+    ///             self.connected.set(true);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// // Called periodically, e.g. from a timer, after the hook was created.
+    /// fn check_connection(hook: &mut FdHook<TcpStream>, connected: &Cell<bool>) {
+    ///     // Once connected, watch the socket for incoming data.
+    ///     if connected.get() && hook.mode() == FdHookMode::WriteOneShot {
+    ///         hook.set_mode(FdHookMode::Read).unwrap();
+    ///     }
+    /// }
+    ///
+    /// # let stream = TcpStream::connect("127.0.0.1:6667").unwrap();
+    /// let connected = Rc::new(Cell::new(false));
+    /// let client = Client {
+    ///     connected: connected.clone(),
+    /// };
+    ///
+    /// let mut hook = FdHook::new(stream, FdHookMode::WriteOneShot, client).unwrap();
+    /// # check_connection(&mut hook, &connected);
+    /// ```
+    pub fn set_mode(&mut self, mode: FdHookMode) -> Result<(), ()>
+    where
+        F: AsRawFd,
+    {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        self.hook_data.hook.take();
+        self.hook_data.mode = mode;
+
+        let hook_ptr = FdHook::hook_fd(&weechat, &*self.hook_data);
 
         if hook_ptr.is_null() {
             Err(())
         } else {
-            Ok(FdHook::<F> {
-                _hook: hook,
-                _hook_data: hook_data,
-            })
+            self.hook_data.hook = Some(Hook {
+                ptr: hook_ptr,
+                weechat_ptr: weechat.ptr,
+            });
+
+            Ok(())
         }
     }
+
+    /// Get the mode the file descriptor is watched with.
+    pub fn mode(&self) -> FdHookMode {
+        self.hook_data.mode
+    }
+
+    /// Is the file descriptor still watched.
+    ///
+    /// Returns false if the hook was created using a one-shot mode and the
+    /// callback already ran.
+    pub fn is_active(&self) -> bool {
+        self.hook_data.hook.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_shot_modes() {
+        assert!(FdHookMode::ReadOneShot.is_one_shot());
+        assert!(FdHookMode::WriteOneShot.is_one_shot());
+        assert!(!FdHookMode::Read.is_one_shot());
+        assert!(!FdHookMode::Write.is_one_shot());
+        assert!(!FdHookMode::ReadWrite.is_one_shot());
+    }
+
+    #[test]
+    fn one_shot_modes_watch_the_same_events() {
+        assert_eq!(
+            FdHookMode::ReadOneShot.as_tuple(),
+            FdHookMode::Read.as_tuple()
+        );
+        assert_eq!(
+            FdHookMode::WriteOneShot.as_tuple(),
+            FdHookMode::Write.as_tuple()
+        );
+        assert_eq!(FdHookMode::ReadWrite.as_tuple(), (1, 1));
+    }
+
+    #[test]
+    fn events_from_poll() {
+        assert_eq!(
+            FdEvents::from_poll(1, libc::POLLIN),
+            FdEvents {
+                readable: true,
+                writable: false,
+            }
+        );
+        assert_eq!(
+            FdEvents::from_poll(1, libc::POLLOUT),
+            FdEvents {
+                readable: false,
+                writable: true,
+            }
+        );
+        assert_eq!(
+            FdEvents::from_poll(1, libc::POLLHUP | libc::POLLOUT),
+            FdEvents {
+                readable: true,
+                writable: true,
+            }
+        );
+    }
+
+    #[test]
+    fn events_from_poll_without_events() {
+        assert_eq!(
+            FdEvents::from_poll(0, 0),
+            FdEvents {
+                readable: false,
+                writable: false,
+            }
+        );
+    }
+
+    #[test]
+    fn events_from_failed_poll() {
+        assert_eq!(
+            FdEvents::from_poll(-1, 0),
+            FdEvents {
+                readable: true,
+                writable: true,
+            }
+        );
+    }
 }
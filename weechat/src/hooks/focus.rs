@@ -0,0 +1,154 @@
+use std::{collections::HashMap, os::raw::c_void, ptr};
+
+use weechat_sys::{t_hashtable, t_weechat_plugin};
+
+use super::Hook;
+use crate::{LossyCString, Weechat};
+
+/// Hook for a focus area, the hook is removed when the object is dropped.
+pub struct FocusHook {
+    _hook: Hook,
+    _hook_data: Box<FocusHookData>,
+}
+
+struct FocusHookData {
+    callback: Box<dyn FocusCallback>,
+    weechat_ptr: *mut t_weechat_plugin,
+}
+
+/// Trait for the focus callback.
+///
+/// A blanket implementation for pure `FnMut` functions exists, if data needs to
+/// be passed to the callback implement this over your struct.
+pub trait FocusCallback {
+    /// Callback that will be called when the area gets the focus.
+    ///
+    /// # Arguments
+    ///
+    /// * `weechat` - A Weechat context.
+    ///
+    /// * `info` - The focus info, e.g. `_x`, `_y`, `_window` or `_bar_item_name`.
+    ///
+    /// Returns the focus info that should be used by Weechat, keys can be
+    /// added, modified or removed.
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        info: HashMap<String, String>,
+    ) -> HashMap<String, String>;
+}
+
+impl<T: FnMut(&Weechat, HashMap<String, String>) -> HashMap<String, String> + 'static> FocusCallback
+    for T
+{
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        info: HashMap<String, String>,
+    ) -> HashMap<String, String> {
+        self(weechat, info)
+    }
+}
+
+impl FocusHook {
+    /// Hook a focus area.
+    ///
+    /// The callback is called when the cursor is moved over the area in cursor
+    /// mode (`/cursor`) or when the area is clicked with the mouse (`/mouse`).
+    /// The info returned by the callback can then be used by key bindings of
+    /// the `cursor` and `mouse` contexts, the keys of the info are available
+    /// as variables in the bound commands, e.g. `${_bar_item_name}`.
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - The area that should be hooked, `chat` for the chat area
+    ///     or the name of a bar item.
+    ///
+    /// * `callback` - A function or a struct that implements FocusCallback,
+    ///     the callback method of the trait will be called when the area gets
+    ///     the focus.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use weechat::Weechat;
+    /// # use weechat::hooks::FocusHook;
+    /// let focus_hook = FocusHook::new(
+    ///     "my_bar_item",
+    ///     |_: &Weechat, mut info: HashMap<String, String>| {
+    ///         info.insert("my_plugin_action".to_owned(), "open".to_owned());
+    ///         info
+    /// });
+    /// ```
+    pub fn new(area: &str, callback: impl FocusCallback + 'static) -> Result<Self, ()> {
+        unsafe extern "C" fn c_hook_cb(
+            pointer: *const c_void,
+            _data: *mut c_void,
+            info: *mut t_hashtable,
+        ) -> *mut t_hashtable {
+            let hook_data: &mut FocusHookData = { &mut *(pointer as *mut FocusHookData) };
+            let cb = &mut hook_data.callback;
+
+            let weechat = Weechat::from_ptr(hook_data.weechat_ptr);
+            let old_info = weechat.hashtable_to_hashmap(info);
+
+            let new_info = cb.callback(&weechat, old_info.clone());
+
+            let hashtable_remove = weechat.get().hashtable_remove.unwrap();
+
+            for key in old_info.keys().filter(|k| !new_info.contains_key(*k)) {
+                let key = LossyCString::new(key);
+                hashtable_remove(info, key.as_ptr() as *const c_void);
+            }
+
+            weechat.hashtable_set_all(info, &new_info);
+
+            // Returning the hashtable we got means that Weechat will use it as
+            // is.
+            info
+        }
+
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let data = Box::new(FocusHookData {
+            callback: Box::new(callback),
+            weechat_ptr: weechat.ptr,
+        });
+
+        let data_ref = Box::leak(data);
+        let hook_focus = weechat.get().hook_focus.unwrap();
+
+        let area = LossyCString::new(area);
+
+        let hook_ptr = unsafe {
+            hook_focus(
+                weechat.ptr,
+                area.as_ptr(),
+                Some(c_hook_cb),
+                data_ref as *const _ as *const c_void,
+                ptr::null_mut(),
+            )
+        };
+
+        let hook_data = unsafe { Box::from_raw(data_ref) };
+        let hook = Hook {
+            ptr: hook_ptr,
+            weechat_ptr: weechat.ptr,
+        };
+
+        if hook_ptr.is_null() {
+            Err(())
+        } else {
+            Ok(FocusHook {
+                _hook: hook,
+                _hook_data: hook_data,
+            })
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::{
+    collections::HashMap,
+    ffi::CStr,
+    os::raw::{c_char, c_int, c_void},
+    ptr,
+};
+
+use weechat_sys::{t_hashtable, t_weechat_plugin};
+
+use super::Hook;
+use crate::{LossyCString, ReturnCode, Weechat};
+
+/// Hook for a hsignal, the hook is removed when the object is dropped.
+pub struct HSignalHook {
+    _hook: Hook,
+    _hook_data: Box<HSignalHookData>,
+}
+
+struct HSignalHookData {
+    callback: Box<dyn HSignalCallback>,
+    weechat_ptr: *mut t_weechat_plugin,
+}
+
+/// Trait for the hsignal callback.
+///
+/// A blanket implementation for pure `FnMut` functions exists, if data needs to
+/// be passed to the callback implement this over your struct.
+pub trait HSignalCallback {
+    /// Callback that will be called when a hsignal is fired.
+    ///
+    /// # Arguments
+    ///
+    /// * `weechat` - A Weechat context.
+    ///
+    /// * `signal_name` - The name of the signal that fired the callback.
+    ///
+    /// * `data` - The hashtable that was sent with the signal.
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        signal_name: &str,
+        data: HashMap<String, String>,
+    ) -> ReturnCode;
+}
+
+impl<T: FnMut(&Weechat, &str, HashMap<String, String>) -> ReturnCode + 'static> HSignalCallback
+    for T
+{
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        signal_name: &str,
+        data: HashMap<String, String>,
+    ) -> ReturnCode {
+        self(weechat, signal_name, data)
+    }
+}
+
+impl HSignalHook {
+    /// Hook a hsignal, a signal that sends out a hashtable.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal_name` - The signal to catch (wildcard `*` is allowed).
+    ///
+    /// * `callback` - A function or a struct that implements HSignalCallback,
+    /// the callback method of the trait will be called when the signal is
+    /// fired.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use weechat::{Weechat, ReturnCode};
+    /// # use weechat::hooks::HSignalHook;
+    /// let hsignal_hook = HSignalHook::new(
+    ///     "irc_redirection_my_plugin_whois",
+    ///     |_weechat: &Weechat, _signal_name: &str, data: HashMap<String, String>| {
+    ///         if let Some(output) = data.get("output") {
+    ///             Weechat::print(output);
+    ///         }
+    ///
+    ///         ReturnCode::Ok
+    /// });
+    /// ```
+    pub fn new(signal_name: &str, callback: impl HSignalCallback + 'static) -> Result<Self, ()> {
+        unsafe extern "C" fn c_hook_cb(
+            pointer: *const c_void,
+            _data: *mut c_void,
+            signal_name: *const c_char,
+            hashtable: *mut t_hashtable,
+        ) -> c_int {
+            let hook_data: &mut HSignalHookData = { &mut *(pointer as *mut HSignalHookData) };
+            let cb = &mut hook_data.callback;
+
+            let signal_name = CStr::from_ptr(signal_name).to_str().unwrap_or_default();
+
+            let weechat = Weechat::from_ptr(hook_data.weechat_ptr);
+            let data = weechat.hashtable_to_hashmap(hashtable);
+
+            cb.callback(&weechat, signal_name, data) as i32
+        }
+
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let data = Box::new(HSignalHookData {
+            callback: Box::new(callback),
+            weechat_ptr: weechat.ptr,
+        });
+
+        let data_ref = Box::leak(data);
+        let hook_hsignal = weechat.get().hook_hsignal.unwrap();
+
+        let signal_name = LossyCString::new(signal_name);
+
+        let hook_ptr = unsafe {
+            hook_hsignal(
+                weechat.ptr,
+                signal_name.as_ptr(),
+                Some(c_hook_cb),
+                data_ref as *const _ as *const c_void,
+                ptr::null_mut(),
+            )
+        };
+
+        let hook_data = unsafe { Box::from_raw(data_ref) };
+        let hook = Hook {
+            ptr: hook_ptr,
+            weechat_ptr: weechat.ptr,
+        };
+
+        if hook_ptr.is_null() {
+            Err(())
+        } else {
+            Ok(HSignalHook {
+                _hook: hook,
+                _hook_data: hook_data,
+            })
+        }
+    }
+}
+
+impl Weechat {
+    /// Send a hsignal, a signal carrying a hashtable.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal_name` - The name of the signal that should be sent.
+    ///
+    /// * `data` - The hashtable that should be sent with the signal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use weechat::Weechat;
+    /// let mut data = HashMap::new();
+    /// data.insert("server", "libera");
+    /// data.insert("pattern", "whois");
+    /// data.insert("signal", "my_plugin");
+    ///
+    /// // Redirect the output of the next whois command to the
+    /// // irc_redirection_my_plugin_whois hsignal.
+    /// Weechat::hook_hsignal_send("irc_redirect_command", &data);
+    /// Weechat::hook_signal_send("irc_input_send", "libera;;2;;/whois nick");
+    /// ```
+    pub fn hook_hsignal_send(signal_name: &str, data: &HashMap<&str, &str>) -> ReturnCode {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let signal_name = LossyCString::new(signal_name);
+        let hsignal_send = weechat.get().hook_hsignal_send.unwrap();
+
+        let hashtable = weechat.hashmap_to_weechat(data.clone());
+        let ret = unsafe { hsignal_send(signal_name.as_ptr(), hashtable) };
+        unsafe { weechat.get().hashtable_free.unwrap()(hashtable) };
+
+        match ret {
+            weechat_sys::WEECHAT_RC_OK => ReturnCode::Ok,
+            weechat_sys::WEECHAT_RC_OK_EAT => ReturnCode::OkEat,
+            weechat_sys::WEECHAT_RC_ERROR => ReturnCode::Error,
+            _ => ReturnCode::Error,
+        }
+    }
+}
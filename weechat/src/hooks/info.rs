@@ -0,0 +1,181 @@
+use libc::c_char;
+use std::{borrow::Cow, ffi::CStr, os::raw::c_void, ptr};
+
+use weechat_sys::t_weechat_plugin;
+
+use super::Hook;
+use crate::{LossyCString, Weechat};
+
+/// Hook for an info, the hook is removed when the object is dropped.
+pub struct InfoHook {
+    _hook: Hook,
+    _hook_data: Box<InfoHookData>,
+}
+
+struct InfoHookData {
+    callback: Box<dyn InfoCallback>,
+    weechat_ptr: *mut t_weechat_plugin,
+}
+
+/// Trait for the info callback.
+///
+/// A blanket implementation for pure `FnMut` functions exists, if data needs to
+/// be passed to the callback implement this over your struct.
+pub trait InfoCallback {
+    /// Callback that will be called when the info is requested.
+    ///
+    /// # Arguments
+    ///
+    /// * `weechat` - A Weechat context.
+    ///
+    /// * `info_name` - The name of the info that was requested.
+    ///
+    /// * `arguments` - The arguments of the info request.
+    ///
+    /// Returns the info, or `None` if no info is available for the given
+    /// arguments.
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        info_name: &str,
+        arguments: Cow<str>,
+    ) -> Option<String>;
+}
+
+impl<T: FnMut(&Weechat, &str, Cow<str>) -> Option<String> + 'static> InfoCallback for T {
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        info_name: &str,
+        arguments: Cow<str>,
+    ) -> Option<String> {
+        self(weechat, info_name, arguments)
+    }
+}
+
+impl InfoHook {
+    /// Provide a new info.
+    ///
+    /// The info can be fetched by other plugins and scripts using
+    /// `info_get()` or by users using `/eval ${info:name,arguments}`.
+    ///
+    /// Info names are global, if multiple infos with the same name exist the
+    /// one that was hooked first is used. Prefix the name of the info with the
+    /// name of the plugin to avoid collisions.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the info.
+    ///
+    /// * `description` - The description of the info.
+    ///
+    /// * `args_description` - The description of the arguments the info
+    ///     accepts.
+    ///
+    /// * `callback` - A function or a struct that implements InfoCallback,
+    ///     the callback method of the trait will be called when the info is
+    ///     requested.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::borrow::Cow;
+    /// # use weechat::Weechat;
+    /// # use weechat::hooks::InfoHook;
+    /// let info_hook = InfoHook::new(
+    ///     "my_plugin_connected",
+    ///     "1 if my plugin is connected to the server, 0 otherwise",
+    ///     "server name",
+    ///     |_: &Weechat, _: &str, server: Cow<str>| {
+    ///         if server.is_empty() {
+    ///             None
+    ///         } else {
+    ///             Some("1".to_owned())
+    ///         }
+    /// });
+    /// ```
+    pub fn new(
+        name: &str,
+        description: &str,
+        args_description: &str,
+        callback: impl InfoCallback + 'static,
+    ) -> Result<Self, ()> {
+        unsafe extern "C" fn c_hook_cb(
+            pointer: *const c_void,
+            _data: *mut c_void,
+            info_name: *const c_char,
+            arguments: *const c_char,
+        ) -> *mut c_char {
+            let hook_data: &mut InfoHookData = { &mut *(pointer as *mut InfoHookData) };
+            let cb = &mut hook_data.callback;
+
+            let info_name = CStr::from_ptr(info_name).to_str().unwrap_or_default();
+
+            let arguments = if arguments.is_null() {
+                Cow::from("")
+            } else {
+                CStr::from_ptr(arguments).to_string_lossy()
+            };
+
+            let weechat = Weechat::from_ptr(hook_data.weechat_ptr);
+
+            if let Some(info) = cb.callback(&weechat, info_name, arguments) {
+                let info_length = info.len();
+                let info = LossyCString::new(info);
+
+                // Weechat frees the returned string, so it needs to be
+                // allocated by Weechat.
+                let strndup = weechat.get().strndup.unwrap();
+                strndup(info.as_ptr(), info_length as i32)
+            } else {
+                ptr::null_mut()
+            }
+        }
+
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let data = Box::new(InfoHookData {
+            callback: Box::new(callback),
+            weechat_ptr: weechat.ptr,
+        });
+
+        let data_ref = Box::leak(data);
+        let hook_info = weechat.get().hook_info.unwrap();
+
+        let name = LossyCString::new(name);
+        let description = LossyCString::new(description);
+        let args_description = LossyCString::new(args_description);
+
+        let hook_ptr = unsafe {
+            hook_info(
+                weechat.ptr,
+                name.as_ptr(),
+                description.as_ptr(),
+                args_description.as_ptr(),
+                Some(c_hook_cb),
+                data_ref as *const _ as *const c_void,
+                ptr::null_mut(),
+            )
+        };
+
+        let hook_data = unsafe { Box::from_raw(data_ref) };
+        let hook = Hook {
+            ptr: hook_ptr,
+            weechat_ptr: weechat.ptr,
+        };
+
+        if hook_ptr.is_null() {
+            Err(())
+        } else {
+            Ok(InfoHook {
+                _hook: hook,
+                _hook_data: hook_data,
+            })
+        }
+    }
+}
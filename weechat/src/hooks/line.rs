@@ -0,0 +1,273 @@
+use std::{collections::HashMap, os::raw::c_void, ptr};
+
+use weechat_sys::{t_hashtable, t_weechat_plugin};
+
+use super::Hook;
+use crate::{LossyCString, Weechat};
+
+/// Hook for lines that are added to buffers, the hook is removed when the
+/// object is dropped.
+pub struct LineHook {
+    _hook: Hook,
+    _hook_data: Box<LineHookData>,
+}
+
+struct LineHookData {
+    callback: Box<dyn LineCallback>,
+    weechat_ptr: *mut t_weechat_plugin,
+}
+
+/// A line that is about to be added to a buffer.
+///
+/// Changes to the public fields are applied to the line once the callback
+/// returns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookLineData {
+    /// The full name of the buffer the line will be added to, e.g.
+    /// `irc.freenode.#weechat`. Changing it moves the line to another
+    /// buffer.
+    pub buffer_name: String,
+    /// The line number, only used for buffers with free content.
+    pub y: i32,
+    /// The date of the line.
+    pub date: i64,
+    /// The date when the line was printed.
+    pub date_printed: i64,
+    /// The formatted date of the line that is displayed.
+    pub str_time: String,
+    /// The tags of the line.
+    pub tags: Vec<String>,
+    /// The notify level of the line, `-1` if the line doesn't add the buffer
+    /// to the hotlist, `0` for low, `1` for message, `2` for private
+    /// messages and `3` for highlights.
+    pub notify_level: i32,
+    /// Is the line a highlight.
+    pub highlight: bool,
+    /// The prefix of the line.
+    pub prefix: String,
+    /// The message of the line.
+    pub message: String,
+    buffer_type: String,
+    displayed: bool,
+}
+
+impl HookLineData {
+    fn from_hashmap(line: &HashMap<String, String>) -> Self {
+        let string = |key: &str| line.get(key).cloned().unwrap_or_default();
+        let integer = |key: &str| {
+            line.get(key)
+                .and_then(|v| v.parse::<i64>().ok())
+                .unwrap_or_default()
+        };
+
+        let tags = line
+            .get("tags")
+            .filter(|t| !t.is_empty())
+            .map(|t| t.split(',').map(|t| t.to_owned()).collect())
+            .unwrap_or_default();
+
+        HookLineData {
+            buffer_name: string("buffer_name"),
+            y: integer("y") as i32,
+            date: integer("date"),
+            date_printed: integer("date_printed"),
+            str_time: string("str_time"),
+            tags,
+            notify_level: integer("notify_level") as i32,
+            highlight: integer("highlight") != 0,
+            prefix: string("prefix"),
+            message: string("message"),
+            buffer_type: string("buffer_type"),
+            displayed: integer("displayed") != 0,
+        }
+    }
+
+    fn changes(&self, old: &HookLineData) -> HashMap<&'static str, String> {
+        let mut changes = HashMap::new();
+
+        if self.buffer_name != old.buffer_name {
+            changes.insert("buffer_name", self.buffer_name.clone());
+        }
+        if self.y != old.y {
+            changes.insert("y", self.y.to_string());
+        }
+        if self.date != old.date {
+            changes.insert("date", self.date.to_string());
+        }
+        if self.date_printed != old.date_printed {
+            changes.insert("date_printed", self.date_printed.to_string());
+        }
+        if self.str_time != old.str_time {
+            changes.insert("str_time", self.str_time.clone());
+        }
+        if self.tags != old.tags {
+            changes.insert("tags", self.tags.join(","));
+        }
+        if self.notify_level != old.notify_level {
+            changes.insert("notify_level", self.notify_level.to_string());
+        }
+        if self.highlight != old.highlight {
+            changes.insert("highlight", (self.highlight as i32).to_string());
+        }
+        if self.prefix != old.prefix {
+            changes.insert("prefix", self.prefix.clone());
+        }
+        if self.message != old.message {
+            changes.insert("message", self.message.clone());
+        }
+
+        changes
+    }
+
+    /// The type of the buffer the line will be added to, `formatted` or
+    /// `free`.
+    pub fn buffer_type(&self) -> &str {
+        &self.buffer_type
+    }
+
+    /// Will the line be displayed, false if the line is filtered.
+    pub fn displayed(&self) -> bool {
+        self.displayed
+    }
+}
+
+/// Trait for the line callback.
+///
+/// A blanket implementation for pure `FnMut` functions exists, if data needs to
+/// be passed to the callback implement this over your struct.
+pub trait LineCallback {
+    /// Callback that will be called before a line is added to a buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `weechat` - A Weechat context.
+    ///
+    /// * `line` - The line that will be added, modifications of the line are
+    ///     sent back to Weechat.
+    fn callback(&mut self, weechat: &Weechat, line: &mut HookLineData);
+}
+
+impl<T: FnMut(&Weechat, &mut HookLineData) + 'static> LineCallback for T {
+    fn callback(&mut self, weechat: &Weechat, line: &mut HookLineData) {
+        self(weechat, line)
+    }
+}
+
+impl LineHook {
+    /// Hook lines before they are added to a buffer.
+    ///
+    /// Unlike a `PrintHook`, which is notified after a line was printed, the
+    /// callback of a line hook runs before the line is stored in the buffer.
+    /// This allows the buffer, tags, prefix, message and notify level of the
+    /// line to be modified, e.g. to redirect messages to another buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer_type` - The type of the buffers whose lines should be caught,
+    ///     `formatted`, `free` or `*` for all buffers. If empty, only lines of
+    ///     formatted buffers are caught.
+    ///
+    /// * `buffer_name` - A comma separated list of buffer masks, e.g.
+    ///     `irc.freenode.*`. If empty, lines of all buffers are caught.
+    ///
+    /// * `tags` - Only catch lines that contain any of the given tags, if empty
+    ///     lines with any tags are caught. To require multiple tags at once,
+    ///     combine them with a `+` inside a single entry, e.g.
+    ///     `irc_notice+nick_NickServ`.
+    ///
+    /// * `callback` - A function or a struct that implements LineCallback,
+    ///     the callback method of the trait will be called when a matching
+    ///     line is added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// # use weechat::hooks::{LineHook, HookLineData};
+    /// let line_hook = LineHook::new(
+    ///     "formatted",
+    ///     "irc.freenode.*",
+    ///     &["irc_notice"],
+    ///     |_: &Weechat, line: &mut HookLineData| {
+    ///         line.buffer_name = "irc.server.freenode".to_owned();
+    /// });
+    /// ```
+    pub fn new(
+        buffer_type: &str,
+        buffer_name: &str,
+        tags: &[&str],
+        callback: impl LineCallback + 'static,
+    ) -> Result<Self, ()> {
+        unsafe extern "C" fn c_hook_cb(
+            pointer: *const c_void,
+            _data: *mut c_void,
+            line: *mut t_hashtable,
+        ) -> *mut t_hashtable {
+            let hook_data: &mut LineHookData = { &mut *(pointer as *mut LineHookData) };
+            let cb = &mut hook_data.callback;
+
+            let weechat = Weechat::from_ptr(hook_data.weechat_ptr);
+
+            let old_line = HookLineData::from_hashmap(&weechat.hashtable_to_hashmap(line));
+            let mut new_line = old_line.clone();
+
+            cb.callback(&weechat, &mut new_line);
+
+            let changes = new_line.changes(&old_line);
+
+            if changes.is_empty() {
+                ptr::null_mut()
+            } else {
+                // Weechat frees the returned hashtable once the changes are
+                // applied.
+                weechat.hashmap_to_weechat(changes.iter().map(|(k, v)| (*k, v.as_str())).collect())
+            }
+        }
+
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let data = Box::new(LineHookData {
+            callback: Box::new(callback),
+            weechat_ptr: weechat.ptr,
+        });
+
+        let data_ref = Box::leak(data);
+        let hook_line = weechat.get().hook_line.unwrap();
+
+        let buffer_type = LossyCString::new(buffer_type);
+        let buffer_name = LossyCString::new(buffer_name);
+        let tags = LossyCString::new(tags.join(","));
+
+        let hook_ptr = unsafe {
+            hook_line(
+                weechat.ptr,
+                buffer_type.as_ptr(),
+                buffer_name.as_ptr(),
+                tags.as_ptr(),
+                Some(c_hook_cb),
+                data_ref as *const _ as *const c_void,
+                ptr::null_mut(),
+            )
+        };
+
+        let hook_data = unsafe { Box::from_raw(data_ref) };
+        let hook = Hook {
+            ptr: hook_ptr,
+            weechat_ptr: weechat.ptr,
+        };
+
+        if hook_ptr.is_null() {
+            Err(())
+        } else {
+            Ok(LineHook {
+                _hook: hook,
+                _hook_data: hook_data,
+            })
+        }
+    }
+}
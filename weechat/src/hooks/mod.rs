@@ -1,26 +1,48 @@
 //! Weechat Hook module.
 //!
 //! Weechat hooks are used for many different things, to create commands, to
-//! listen to events on a file descriptor, add completions to Weechat, etc.
+//! listen to events on a file descriptor, add completions to Weechat, watch
+//! config options for changes, etc.
 
 mod signal;
 
 mod bar;
 mod commands;
 mod completion;
+mod config;
 mod fd;
-#[cfg(feature = "unsound")]
+mod focus;
+mod hsignal;
+mod info;
+mod line;
 mod modifier;
+mod print;
+mod process;
 mod timer;
 
-pub use bar::{BarItem, BarItemCallback};
-pub use commands::{Command, CommandCallback, CommandRun, CommandRunCallback, CommandSettings};
+pub use bar::{BarItem, BarItemCallback, BarItemWindowCallback};
+#[cfg(feature = "async")]
+pub use commands::CommandCallbackAsync;
+pub use commands::{
+    Command, CommandCallback, CommandGuard, CommandRun, CommandRunCallback, CommandRunSettings,
+    CommandSettings,
+};
 pub use completion::{Completion, CompletionCallback, CompletionHook, CompletionPosition};
+pub use config::{ConfigHook, ConfigHookCallback};
 
-pub use fd::{FdHook, FdHookCallback, FdHookMode};
-#[cfg(feature = "unsound")]
+pub use fd::{FdEvents, FdHook, FdHookCallback, FdHookMode};
+pub use focus::{FocusCallback, FocusHook};
+pub use hsignal::{HSignalCallback, HSignalHook};
+pub use info::{InfoCallback, InfoHook};
+pub use line::{HookLineData, LineCallback, LineHook};
 pub use modifier::{ModifierCallback, ModifierData, ModifierHook};
-pub use signal::{SignalCallback, SignalData, SignalHook};
+pub use print::{PrintCallback, PrintHook, PrintedLine};
+#[cfg(feature = "async")]
+pub use process::ProcessOutput;
+pub use process::{ProcessCallback, ProcessHook, ProcessStatus};
+pub use signal::{SignalCallback, SignalData, SignalDate, SignalHook, SignalRouter};
+#[cfg(feature = "async")]
+pub use timer::IntervalStream;
 pub use timer::{RemainingCalls, TimerCallback, TimerHook};
 
 use crate::Weechat;
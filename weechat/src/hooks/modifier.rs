@@ -1,13 +1,15 @@
 use libc::c_char;
 use std::{borrow::Cow, ffi::CStr, os::raw::c_void, ptr};
 
-use weechat_sys::{t_gui_buffer, t_weechat_plugin};
+use weechat_sys::{t_gui_buffer, t_gui_window, t_weechat_plugin};
 
 use super::Hook;
-use crate::{buffer::Buffer, LossyCString, Weechat};
+use crate::{
+    buffer::{Buffer, Window},
+    LossyCString, Weechat,
+};
 
 /// Hook for a modifier, the hook is removed when the object is dropped.
-#[cfg_attr(feature = "docs", doc(cfg(unsound)))]
 pub struct ModifierHook {
     _hook: Hook,
     _hook_data: Box<ModifierHookData>,
@@ -19,33 +21,129 @@ struct ModifierHookData {
 }
 
 /// Enum over the different data types a modifier may send.
+///
+/// The data of the modifiers Weechat and its plugins provide is parsed
+/// according to the format documented in the [Weechat plugin API
+/// docs](https://weechat.org/files/doc/stable/weechat_plugin_api.en.html#_hook_modifier):
+///
+/// | Modifier                         | Data                       |
+/// | -------------------------------- | -------------------------- |
+/// | `bar_condition_*`                | `Window`                   |
+/// | `history_add`                    | `Buffer`                   |
+/// | `input_text_content`             | `Buffer`                   |
+/// | `input_text_display`             | `Buffer`                   |
+/// | `input_text_display_with_cursor` | `Buffer`                   |
+/// | `input_text_for_buffer`          | `Buffer`                   |
+/// | `weechat_print`                  | `Print`                    |
+/// | `charset_decode`                 | `Charset`                  |
+/// | `charset_encode`                 | `Charset`                  |
+/// | `color_decode_ansi`              | `KeepColors`               |
+/// | `irc_color_decode`               | `KeepColors`               |
+/// | `irc_color_encode`               | `KeepColors`               |
+/// | `irc_command_auth`               | `Server`                   |
+/// | `irc_in_*`, `irc_in2_*`          | `Server`                   |
+/// | `irc_out1_*`, `irc_out_*`        | `Server`                   |
+///
+/// The data of any other modifier is passed on as a `String`.
+///
+/// Buffer and window pointers are checked against the list of existing
+/// buffers and windows, if the modifier data contains a pointer that doesn't
+/// belong to one, no data is passed to the callback.
 pub enum ModifierData<'a> {
     /// String data
     String(Cow<'a, str>),
     /// Buffer that was sent with the modifier.
     Buffer(Buffer<'a>),
+    /// Window that was sent with the modifier.
+    Window(Window<'a>),
+    /// The name of the IRC server the message belongs to.
+    Server(Cow<'a, str>),
+    /// Should the colors be kept or stripped while converting.
+    KeepColors(bool),
+    /// The plugin and buffer name of the string that is converted.
+    Charset {
+        /// The name of the plugin, e.g. `irc`.
+        plugin: Cow<'a, str>,
+        /// The name of the buffer, e.g. `libera.#weechat`. Can be empty if
+        /// the string doesn't belong to a buffer.
+        buffer_name: Cow<'a, str>,
+    },
+    /// The buffer and the tags of a line that is printed.
+    Print {
+        /// The buffer the line is printed to.
+        buffer: Buffer<'a>,
+        /// The tags of the line.
+        tags: Vec<Cow<'a, str>>,
+    },
 }
 
 impl<'a> ModifierData<'a> {
-    fn pointer_is_buffer(modifier_name: &str) -> bool {
-        // This table is taken from the Weechat plugin API docs
-        //
-        // https://weechat.org/files/doc/stable/weechat_plugin_api.en.html#_hook_modifier
-        if modifier_name.starts_with("bar_condition_") {
-            true
+    fn split(data: Cow<'a, str>, separator: char, count: usize) -> Vec<Cow<'a, str>> {
+        match data {
+            Cow::Borrowed(data) => data.splitn(count, separator).map(Cow::from).collect(),
+            Cow::Owned(data) => data
+                .splitn(count, separator)
+                .map(|part| Cow::from(part.to_owned()))
+                .collect(),
+        }
+    }
+
+    fn parse_pointer(data: &str) -> Option<*mut c_void> {
+        let pointer = data.strip_prefix("0x")?;
+        let pointer = usize::from_str_radix(pointer, 16).ok()?;
+
+        Some(pointer as *mut c_void)
+    }
+
+    /// Parse a pointer and check that it points to an item of the given
+    /// hdata list.
+    fn checked_pointer(
+        weechat: &Weechat,
+        data: &str,
+        hdata_name: &str,
+        list_name: &str,
+    ) -> Option<*mut c_void> {
+        let pointer = ModifierData::parse_pointer(data)?;
+
+        let valid = unsafe {
+            let hdata = weechat.hdata_get(hdata_name);
+            let list = weechat.hdata_get_list(hdata, list_name);
+            weechat.hdata_check_pointer(hdata, list, pointer)
+        };
+
+        if valid {
+            Some(pointer)
         } else {
-            matches!(
-                modifier_name,
-                "bar_condition_yyy"
-                    | "history_add"
-                    | "input_text_content"
-                    | "input_text_display"
-                    | "input_text_display_with_cursor"
-                    | "input_text_for_buffer"
-            )
+            None
         }
     }
 
+    fn buffer(weechat: &'a Weechat, data: &str) -> Option<Buffer<'a>> {
+        let pointer = ModifierData::checked_pointer(weechat, data, "buffer", "gui_buffers")?;
+        Some(weechat.buffer_from_ptr(pointer as *mut t_gui_buffer))
+    }
+
+    fn print(weechat: &'a Weechat, data: Cow<'a, str>) -> Option<ModifierData<'a>> {
+        let mut parts = ModifierData::split(data, ';', 3).into_iter();
+        let first = parts.next()?;
+
+        // Weechat sends the buffer pointer and the tags, older versions sent
+        // the plugin name, the buffer name and the tags instead.
+        let (buffer, tags) = if first.starts_with("0x") {
+            (ModifierData::buffer(weechat, &first)?, parts.next())
+        } else {
+            let buffer_name = parts.next()?;
+            (weechat.buffer_search(&first, &buffer_name)?, parts.next())
+        };
+
+        let tags = tags
+            .filter(|tags| !tags.is_empty())
+            .map(|tags| ModifierData::split(tags, ',', usize::MAX))
+            .unwrap_or_default();
+
+        Some(ModifierData::Print { buffer, tags })
+    }
+
     fn from_name(
         weechat: &'a Weechat,
         modifier_name: &str,
@@ -57,21 +155,45 @@ impl<'a> ModifierData<'a> {
 
         let modifier_data = unsafe { CStr::from_ptr(data).to_string_lossy() };
 
-        // Some modifier send out a buffer pointer converted to a string,
-        // convert those to a buffer.
-        if ModifierData::pointer_is_buffer(modifier_name) {
-            if modifier_data.len() < 2 || !modifier_data.starts_with("0x") {
-                None
-            } else {
-                let ptr = u64::from_str_radix(&modifier_data[2..], 16).ok()?;
+        let is_server_modifier = ["irc_in_", "irc_in2_", "irc_out1_", "irc_out_"]
+            .iter()
+            .any(|prefix| modifier_name.starts_with(prefix));
 
-                Some(ModifierData::Buffer(
-                    weechat.buffer_from_ptr(ptr as *mut t_gui_buffer),
-                ))
+        let data = match modifier_name {
+            name if name.starts_with("bar_condition_") => {
+                let pointer = ModifierData::checked_pointer(
+                    weechat,
+                    &modifier_data,
+                    "window",
+                    "gui_windows",
+                )?;
+                ModifierData::Window(weechat.window_from_ptr(pointer as *mut t_gui_window))
             }
-        } else {
-            Some(ModifierData::String(modifier_data))
-        }
+            "history_add"
+            | "input_text_content"
+            | "input_text_display"
+            | "input_text_display_with_cursor"
+            | "input_text_for_buffer" => {
+                ModifierData::Buffer(ModifierData::buffer(weechat, &modifier_data)?)
+            }
+            "weechat_print" => ModifierData::print(weechat, modifier_data)?,
+            "charset_decode" | "charset_encode" => {
+                let mut parts = ModifierData::split(modifier_data, '.', 2).into_iter();
+
+                ModifierData::Charset {
+                    plugin: parts.next().unwrap_or_default(),
+                    buffer_name: parts.next().unwrap_or_default(),
+                }
+            }
+            "color_decode_ansi" | "irc_color_decode" | "irc_color_encode" => {
+                ModifierData::KeepColors(modifier_data == "1")
+            }
+            "irc_command_auth" => ModifierData::Server(modifier_data),
+            _ if is_server_modifier => ModifierData::Server(modifier_data),
+            _ => ModifierData::String(modifier_data),
+        };
+
+        Some(data)
     }
 }
 
@@ -128,6 +250,13 @@ impl<T: FnMut(&Weechat, &str, Option<ModifierData>, Cow<str>) -> Option<String>
 impl ModifierHook {
     /// Hook a modifier.
     ///
+    /// Modifiers don't need to be registered, hooking a modifier with a new
+    /// name provides a new modifier that other plugins and scripts can run
+    /// using `Weechat::execute_modifier()`.
+    ///
+    /// If the callback returns `None` the string is passed through unchanged,
+    /// if it returns an empty string the string is dropped.
+    ///
     /// # Arguments
     ///
     /// * `modifier_name` - The modifier to hook.
@@ -159,8 +288,19 @@ impl ModifierHook {
     ///
     ///     None
     /// });
+    ///
+    /// // Provide a new modifier that upper-cases the input string.
+    /// let custom_modifier = ModifierHook::new(
+    /// "my_plugin_uppercase",
+    /// |_weechat: &Weechat,
+    /// _modifier_name: &str,
+    /// _data: Option<ModifierData>,
+    /// string: Cow<str>| {
+    ///     Some(string.to_uppercase())
+    /// });
+    ///
+    /// let modified = Weechat::execute_modifier("my_plugin_uppercase", "", "hello");
     /// ```
-    #[cfg_attr(feature = "docs", doc(cfg(unsound)))]
     pub fn new(modifier_name: &str, callback: impl ModifierCallback + 'static) -> Result<Self, ()> {
         unsafe extern "C" fn c_hook_cb(
             pointer: *const c_void,
@@ -0,0 +1,286 @@
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    ffi::CStr,
+    os::raw::{c_char, c_int, c_void},
+    ptr,
+    rc::Rc,
+};
+
+use weechat_sys::{t_gui_buffer, t_weechat_plugin};
+
+use super::Hook;
+use crate::{buffer::Buffer, LossyCString, ReturnCode, Weechat};
+
+/// Hook for printed lines, the hook is removed when the object is dropped.
+pub struct PrintHook {
+    _hook: Hook,
+    _hook_data: Box<PrintHookData>,
+}
+
+struct PrintHookData {
+    callback: Box<dyn PrintCallback>,
+    weechat_ptr: *mut t_weechat_plugin,
+}
+
+/// A line that was printed to a buffer.
+pub struct PrintedLine<'a> {
+    /// The date of the line.
+    pub date: i64,
+    /// The tags of the line.
+    pub tags: Vec<Cow<'a, str>>,
+    /// Is the line displayed, false if the line is filtered.
+    pub displayed: bool,
+    /// Is the line a highlight.
+    pub highlight: bool,
+    /// The prefix of the line.
+    pub prefix: Cow<'a, str>,
+    /// The message of the line.
+    pub message: Cow<'a, str>,
+}
+
+/// Trait for the print callback.
+///
+/// A blanket implementation for pure `FnMut` functions exists, if data needs to
+/// be passed to the callback implement this over your struct.
+pub trait PrintCallback {
+    /// Callback that will be called when a line is printed.
+    ///
+    /// # Arguments
+    ///
+    /// * `weechat` - A Weechat context.
+    ///
+    /// * `buffer` - The buffer the line was printed to.
+    ///
+    /// * `line` - The line that was printed.
+    fn callback(&mut self, weechat: &Weechat, buffer: &Buffer, line: PrintedLine) -> ReturnCode;
+}
+
+impl<T: FnMut(&Weechat, &Buffer, PrintedLine) -> ReturnCode + 'static> PrintCallback for T {
+    fn callback(&mut self, weechat: &Weechat, buffer: &Buffer, line: PrintedLine) -> ReturnCode {
+        self(weechat, buffer, line)
+    }
+}
+
+impl PrintHook {
+    /// Hook lines that are printed to buffers.
+    ///
+    /// The line is already printed when the callback is called, it's not
+    /// possible to hide the line by returning `ReturnCode::OkEat` from the
+    /// callback.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - The buffer of the printed lines, if `None` lines printed to
+    ///     any buffer are caught.
+    ///
+    /// * `tags` - Only catch lines that contain any of the given tags, if empty
+    ///     lines with any tags are caught. To require multiple tags at once,
+    ///     combine them with a `+` inside a single entry, e.g.
+    ///     `irc_privmsg+notify_message`.
+    ///
+    /// * `message` - Only catch lines that contain the given string, matching
+    ///     is case insensitive.
+    ///
+    /// * `strip_colors` - Should colors be stripped from the message before the
+    ///     callback is called.
+    ///
+    /// * `callback` - A function or a struct that implements PrintCallback,
+    ///     the callback method of the trait will be called when a matching line
+    ///     is printed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::{Weechat, ReturnCode};
+    /// # use weechat::buffer::Buffer;
+    /// # use weechat::hooks::{PrintHook, PrintedLine};
+    /// let print_hook = PrintHook::new(
+    ///     None,
+    ///     &["irc_privmsg"],
+    ///     None,
+    ///     true,
+    ///     |_: &Weechat, buffer: &Buffer, line: PrintedLine| {
+    ///         if line.highlight {
+    ///             Weechat::print(&format!("Highlight in {}: {}", buffer.name(), line.message));
+    ///         }
+    ///
+    ///         ReturnCode::Ok
+    /// });
+    /// ```
+    pub fn new(
+        buffer: Option<&Buffer>,
+        tags: &[&str],
+        message: Option<&str>,
+        strip_colors: bool,
+        callback: impl PrintCallback + 'static,
+    ) -> Result<Self, ()> {
+        #[allow(clippy::too_many_arguments)]
+        unsafe extern "C" fn c_hook_cb(
+            pointer: *const c_void,
+            _data: *mut c_void,
+            buffer: *mut t_gui_buffer,
+            date: libc::time_t,
+            tags_count: c_int,
+            tags: *mut *const c_char,
+            displayed: c_int,
+            highlight: c_int,
+            prefix: *const c_char,
+            message: *const c_char,
+        ) -> c_int {
+            let hook_data: &mut PrintHookData = { &mut *(pointer as *mut PrintHookData) };
+            let cb = &mut hook_data.callback;
+
+            let weechat = Weechat::from_ptr(hook_data.weechat_ptr);
+            let buffer = weechat.buffer_from_ptr(buffer);
+
+            let to_cow = |string: *const c_char| {
+                if string.is_null() {
+                    Cow::from("")
+                } else {
+                    CStr::from_ptr(string).to_string_lossy()
+                }
+            };
+
+            let tags = if tags.is_null() {
+                Vec::new()
+            } else {
+                (0..tags_count as isize)
+                    .map(|i| to_cow(*tags.offset(i)))
+                    .collect()
+            };
+
+            let line = PrintedLine {
+                date: date as i64,
+                tags,
+                displayed: displayed != 0,
+                highlight: highlight != 0,
+                prefix: to_cow(prefix),
+                message: to_cow(message),
+            };
+
+            cb.callback(&weechat, &buffer, line) as i32
+        }
+
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let data = Box::new(PrintHookData {
+            callback: Box::new(callback),
+            weechat_ptr: weechat.ptr,
+        });
+
+        let data_ref = Box::leak(data);
+        let hook_print = weechat.get().hook_print.unwrap();
+
+        let buffer_ptr = buffer.map(|b| b.ptr()).unwrap_or(ptr::null_mut());
+        let tags = LossyCString::new(tags.join(","));
+        let message = message.map(LossyCString::new);
+
+        let hook_ptr = unsafe {
+            hook_print(
+                weechat.ptr,
+                buffer_ptr,
+                tags.as_ptr(),
+                message.as_ref().map(|m| m.as_ptr()).unwrap_or(ptr::null()),
+                strip_colors as i32,
+                Some(c_hook_cb),
+                data_ref as *const _ as *const c_void,
+                ptr::null_mut(),
+            )
+        };
+
+        let hook_data = unsafe { Box::from_raw(data_ref) };
+        let hook = Hook {
+            ptr: hook_ptr,
+            weechat_ptr: weechat.ptr,
+        };
+
+        if hook_ptr.is_null() {
+            Err(())
+        } else {
+            Ok(PrintHook {
+                _hook: hook,
+                _hook_data: hook_data,
+            })
+        }
+    }
+}
+
+impl Weechat {
+    /// Run a command and capture the lines it prints to the buffer.
+    ///
+    /// A print hook for the buffer is installed while the command runs, the
+    /// messages of all the lines printed to the buffer during that time are
+    /// returned with colors stripped. The hook is removed before this method
+    /// returns.
+    ///
+    /// # Caveats
+    ///
+    /// * Only output that is printed synchronously while the command runs is
+    ///     captured, output that is printed later, e.g. after a reply from a
+    ///     server arrives, is missed.
+    ///
+    /// * Only lines printed to the given buffer are captured, commands that
+    ///     print to a different buffer, e.g. the core buffer, won't produce
+    ///     any output.
+    ///
+    /// * Every line printed to the buffer during the command is captured,
+    ///     this includes lines printed by other plugins that react to the
+    ///     command. Nested captures for the same buffer will both capture the
+    ///     inner command's output.
+    ///
+    /// * The lines are still printed to the buffer, they are not hidden.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - The buffer the command should run on.
+    ///
+    /// * `command` - The command that should run, it should start with a
+    ///     slash.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// # let weechat = unsafe { Weechat::weechat() };
+    /// # let buffer = weechat.buffer_search("core", "weechat").unwrap();
+    /// for line in Weechat::capture_command(&buffer, "/filter list") {
+    ///     Weechat::print(&format!("captured: {}", line));
+    /// }
+    /// ```
+    pub fn capture_command(buffer: &Buffer, command: &str) -> Vec<String> {
+        let lines = Rc::new(RefCell::new(Vec::new()));
+        let lines_ref = Rc::clone(&lines);
+
+        let hook = PrintHook::new(
+            Some(buffer),
+            &[],
+            None,
+            true,
+            move |_: &Weechat, _: &Buffer, line: PrintedLine| {
+                lines_ref.borrow_mut().push(line.message.into_owned());
+                ReturnCode::Ok
+            },
+        );
+
+        // Running the command without the hook would lose the output, so
+        // don't run it at all.
+        if hook.is_err() {
+            return Vec::new();
+        }
+
+        let _ = buffer.run_command(command);
+        drop(hook);
+
+        lines.take()
+    }
+}
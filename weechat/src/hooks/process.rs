@@ -0,0 +1,323 @@
+use libc::{c_char, c_int};
+use std::{
+    borrow::Cow, cell::Cell, convert::TryFrom, ffi::CStr, os::raw::c_void, ptr, time::Duration,
+};
+
+use weechat_sys::{
+    t_hook, t_weechat_plugin, WEECHAT_HOOK_PROCESS_ERROR, WEECHAT_HOOK_PROCESS_RUNNING,
+    WEECHAT_RC_OK,
+};
+
+use crate::{LossyCString, Weechat};
+
+#[cfg(feature = "async")]
+use futures::channel::oneshot;
+
+/// The state of a process that was started by a `ProcessHook`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessStatus {
+    /// The process is still running, more output will follow.
+    Running,
+    /// The process has finished with the given exit code.
+    Finished(i32),
+    /// The process couldn't be started or was killed because the timeout
+    /// was reached.
+    Error,
+}
+
+impl From<i32> for ProcessStatus {
+    fn from(return_code: i32) -> Self {
+        match return_code {
+            WEECHAT_HOOK_PROCESS_RUNNING => ProcessStatus::Running,
+            WEECHAT_HOOK_PROCESS_ERROR => ProcessStatus::Error,
+            r if r >= 0 => ProcessStatus::Finished(r),
+            _ => ProcessStatus::Error,
+        }
+    }
+}
+
+/// Trait for the process callback
+///
+/// A blanket implementation for pure `FnMut` functions exists, if data needs to
+/// be passed to the callback implement this over your struct.
+pub trait ProcessCallback {
+    /// Callback that will be called when the process produces output or
+    /// finishes.
+    ///
+    /// The callback may be called multiple times while the process is running,
+    /// every call contains only the new output since the last call.
+    ///
+    /// # Arguments
+    ///
+    /// * `weechat` - A Weechat context.
+    ///
+    /// * `command` - The command that was run.
+    ///
+    /// * `status` - The state of the process.
+    ///
+    /// * `stdout` - The standard output of the command.
+    ///
+    /// * `stderr` - The error output of the command.
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        command: Cow<str>,
+        status: ProcessStatus,
+        stdout: Cow<str>,
+        stderr: Cow<str>,
+    );
+}
+
+impl<T: FnMut(&Weechat, Cow<str>, ProcessStatus, Cow<str>, Cow<str>) + 'static> ProcessCallback
+    for T
+{
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        command: Cow<str>,
+        status: ProcessStatus,
+        stdout: Cow<str>,
+        stderr: Cow<str>,
+    ) {
+        self(weechat, command, status, stdout, stderr)
+    }
+}
+
+/// A hook for an external process, the hook will be removed when the object is
+/// dropped and the process will be killed if it's still running.
+pub struct ProcessHook {
+    ptr: *mut t_hook,
+    weechat_ptr: *mut t_weechat_plugin,
+    hook_data: Box<ProcessHookData>,
+}
+
+struct ProcessHookData {
+    callback: Box<dyn ProcessCallback>,
+    weechat_ptr: *mut t_weechat_plugin,
+    finished: Cell<bool>,
+}
+
+impl ProcessHook {
+    /// Run an external command without blocking Weechat.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command that should be run, the command is run in a
+    ///     shell. A command prefixed with `url:` will download the given URL
+    ///     instead.
+    ///
+    /// * `timeout` - The time after which the process will be killed, a
+    ///     duration of 0 means no timeout. Weechat stores the timeout in
+    ///     milliseconds as a 32 bit integer, longer timeouts are clamped to
+    ///     `i32::MAX` milliseconds, about 24.8 days.
+    ///
+    /// * `callback` - A function that will be called when the process produces
+    ///     output or finishes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::borrow::Cow;
+    /// # use std::time::Duration;
+    /// # use weechat::Weechat;
+    /// # use weechat::hooks::{ProcessHook, ProcessStatus};
+    ///
+    /// let hook = ProcessHook::new(
+    ///     "git rev-parse HEAD",
+    ///     Duration::from_secs(10),
+    ///     |_: &Weechat, _: Cow<str>, status: ProcessStatus, stdout: Cow<str>, _: Cow<str>| {
+    ///         if let ProcessStatus::Finished(0) = status {
+    ///             Weechat::print(&format!("Current commit: {}", stdout.trim()));
+    ///         }
+    ///     },
+    /// ).expect("Can't run the process");
+    /// ```
+    pub fn new(
+        command: &str,
+        timeout: Duration,
+        callback: impl ProcessCallback + 'static,
+    ) -> Result<ProcessHook, ()> {
+        unsafe extern "C" fn c_hook_cb(
+            pointer: *const c_void,
+            _data: *mut c_void,
+            command: *const c_char,
+            return_code: c_int,
+            out: *const c_char,
+            err: *const c_char,
+        ) -> c_int {
+            let hook_data: &mut ProcessHookData = { &mut *(pointer as *mut ProcessHookData) };
+
+            let to_cow = |string: *const c_char| {
+                if string.is_null() {
+                    Cow::from("")
+                } else {
+                    CStr::from_ptr(string).to_string_lossy()
+                }
+            };
+
+            let status = ProcessStatus::from(return_code);
+
+            // Weechat removes the hook on its own once the process is done,
+            // remember this so we don't unhook it again on drop.
+            if status != ProcessStatus::Running {
+                hook_data.finished.set(true);
+            }
+
+            hook_data.callback.callback(
+                &Weechat::from_ptr(hook_data.weechat_ptr),
+                to_cow(command),
+                status,
+                to_cow(out),
+                to_cow(err),
+            );
+
+            WEECHAT_RC_OK
+        }
+
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let data = Box::new(ProcessHookData {
+            callback: Box::new(callback),
+            weechat_ptr: weechat.ptr,
+            finished: Cell::new(false),
+        });
+
+        let data_ref = Box::leak(data);
+        let hook_process = weechat.get().hook_process.unwrap();
+        let command = LossyCString::new(command);
+
+        let hook_ptr = unsafe {
+            hook_process(
+                weechat.ptr,
+                command.as_ptr(),
+                i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX),
+                Some(c_hook_cb),
+                data_ref as *const _ as *const c_void,
+                ptr::null_mut(),
+            )
+        };
+        let hook_data = unsafe { Box::from_raw(data_ref) };
+
+        if hook_ptr.is_null() {
+            Err(())
+        } else {
+            Ok(ProcessHook {
+                ptr: hook_ptr,
+                weechat_ptr: weechat.ptr,
+                hook_data,
+            })
+        }
+    }
+
+    /// Has the process finished running.
+    pub fn is_finished(&self) -> bool {
+        self.hook_data.finished.get()
+    }
+}
+
+impl Drop for ProcessHook {
+    fn drop(&mut self) {
+        if !self.is_finished() {
+            let weechat = Weechat::from_ptr(self.weechat_ptr);
+            let unhook = weechat.get().unhook.unwrap();
+            unsafe { unhook(self.ptr) };
+        }
+    }
+}
+
+/// The output of a process that was run using `Weechat::run_process()`.
+#[cfg(feature = "async")]
+#[cfg_attr(feature = "docs", doc(cfg(r#async)))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessOutput {
+    /// The exit code of the process, `None` if the process couldn't be
+    /// started or was killed because the timeout was reached.
+    pub return_code: Option<i32>,
+    /// The standard output of the process.
+    pub stdout: String,
+    /// The error output of the process.
+    pub stderr: String,
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(feature = "docs", doc(cfg(r#async)))]
+impl Weechat {
+    /// Run an external command without blocking Weechat and wait for it to
+    /// finish.
+    ///
+    /// The output of the command is collected until the command finishes.
+    /// Dropping the returned future removes the process hook and kills the
+    /// process if it's still running.
+    ///
+    /// Returns an error if the process couldn't be hooked.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command that should be run, the command is run in a
+    ///     shell.
+    ///
+    /// * `timeout` - The time after which the process will be killed, a
+    ///     duration of 0 means no timeout. Weechat stores the timeout in
+    ///     milliseconds as a 32 bit integer, longer timeouts are clamped to
+    ///     `i32::MAX` milliseconds, about 24.8 days.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use weechat::Weechat;
+    /// Weechat::spawn(async {
+    ///     let output = Weechat::run_process("git rev-parse HEAD", Duration::from_secs(10))
+    ///         .await
+    ///         .expect("Can't run the process");
+    ///
+    ///     if output.return_code == Some(0) {
+    ///         Weechat::print(&format!("Current commit: {}", output.stdout.trim()));
+    ///     }
+    /// })
+    /// .detach();
+    /// ```
+    pub async fn run_process(command: &str, timeout: Duration) -> Result<ProcessOutput, ()> {
+        let (sender, receiver) = oneshot::channel();
+
+        let mut sender = Some(sender);
+        let mut stdout = String::new();
+        let mut stderr = String::new();
+
+        let _hook = ProcessHook::new(
+            command,
+            timeout,
+            move |_: &Weechat, _: Cow<str>, status: ProcessStatus, out: Cow<str>, err: Cow<str>| {
+                stdout.push_str(&out);
+                stderr.push_str(&err);
+
+                let return_code = match status {
+                    ProcessStatus::Running => return,
+                    ProcessStatus::Finished(code) => Some(code),
+                    ProcessStatus::Error => None,
+                };
+
+                if let Some(sender) = sender.take() {
+                    let output = ProcessOutput {
+                        return_code,
+                        stdout: std::mem::take(&mut stdout),
+                        stderr: std::mem::take(&mut stderr),
+                    };
+
+                    // The receiver is gone if the future was dropped, there's
+                    // nobody left to care about the output.
+                    let _ = sender.send(output);
+                }
+            },
+        )?;
+
+        receiver.await.map_err(|_| ())
+    }
+}
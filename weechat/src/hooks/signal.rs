@@ -1,17 +1,18 @@
 use std::{
     borrow::Cow,
     cell::Cell,
+    collections::HashMap,
     ffi::CStr,
     os::raw::{c_char, c_int, c_void},
     ptr,
     rc::Rc,
 };
 
-use weechat_sys::{t_gui_buffer, t_weechat_plugin};
+use weechat_sys::{t_gui_buffer, t_gui_window, t_weechat_plugin};
 
 use super::Hook;
 use crate::{
-    buffer::{Buffer, InnerBuffer, InnerBuffers},
+    buffer::{Buffer, BufferLine, InnerBuffer, InnerBuffers, Window},
     LossyCString, ReturnCode, Weechat,
 };
 
@@ -35,6 +36,24 @@ pub enum SignalData<'a> {
     Integer(i32),
     /// Buffer that was sent with the signal.
     Buffer(Buffer<'a>),
+    /// Buffer line that was sent with the signal.
+    BufferLine(BufferLine<'a>),
+    /// Window that was sent with the signal.
+    Window(Window<'a>),
+    /// A pointer that was sent with the signal, used for signals that send
+    /// out pointers to objects which don't have a safe wrapper, e.g.
+    /// infolists or nicklist groups, or pointers of signals that are unknown
+    /// to this crate.
+    ///
+    /// # Safety
+    ///
+    /// The type of the object the pointer points to depends on the signal,
+    /// consult the documentation of the signal. The pointer is only
+    /// guaranteed to be valid during the signal callback, it must not be
+    /// stored and dereferenced later. A signal sent by a third party plugin
+    /// may contain any pointer, make sure to only dereference pointers of
+    /// signals whose sender is trusted.
+    Pointer(*mut c_void),
 }
 
 impl<'a> Into<SignalData<'a>> for &'a str {
@@ -116,12 +135,26 @@ impl<'a> SignalData<'a> {
             // TODO nicklist group signals have a string representation of a
             // pointer concatenated to the group name
 
-            // TODO some signals send out pointers to windows.
             // TODO some signals send out pointers to infolists.
             _ => false,
         }
     }
 
+    fn pointer_is_window(signal_name: &str) -> bool {
+        matches!(
+            signal_name,
+            "window_closed"
+                | "window_closing"
+                | "window_opened"
+                | "window_scrolled"
+                | "window_switch"
+                | "window_unzoom"
+                | "window_unzoomed"
+                | "window_zoom"
+                | "window_zoomed"
+        )
+    }
+
     fn from_type_and_name(
         weechat: &'a Weechat,
         signal_name: &str,
@@ -150,8 +183,15 @@ impl<'a> SignalData<'a> {
                     Some(SignalData::Buffer(
                         weechat.buffer_from_ptr(data as *mut t_gui_buffer),
                     ))
+                } else if SignalData::pointer_is_window(signal_name) {
+                    Some(SignalData::Window(
+                        weechat.window_from_ptr(data as *mut t_gui_window),
+                    ))
+                } else if signal_name == "buffer_line_added" {
+                    BufferLine::from_line_ptr(Weechat::from_ptr(weechat.ptr), data)
+                        .map(SignalData::BufferLine)
                 } else {
-                    None
+                    Some(SignalData::Pointer(data))
                 }
             }
             _ => None,
@@ -159,6 +199,36 @@ impl<'a> SignalData<'a> {
     }
 }
 
+/// A calendar date, as sent by the `day_changed` signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignalDate {
+    /// The year of the date.
+    pub year: i32,
+    /// The month of the date, starting at 1.
+    pub month: u32,
+    /// The day of the month, starting at 1.
+    pub day: u32,
+}
+
+impl SignalDate {
+    /// Parse a date in the `YYYY-MM-DD` format.
+    fn parse(date: &str) -> Option<SignalDate> {
+        let mut split = date.trim().splitn(3, '-');
+
+        let year = split.next()?.parse().ok()?;
+        let month = split.next()?.parse().ok()?;
+        let day = split.next()?.parse().ok()?;
+
+        Some(SignalDate { year, month, day })
+    }
+}
+
+impl std::fmt::Display for SignalDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
 /// Trait for the signal callback.
 ///
 /// A blanket implementation for pure `FnMut` functions exists, if data needs to
@@ -289,6 +359,347 @@ impl SignalHook {
             })
         }
     }
+
+    /// Hook the `plugin_loaded` signal.
+    ///
+    /// The signal is sent out by Weechat after a plugin has been loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback that will be called when a plugin is
+    /// loaded, the callback receives the path of the plugin that was loaded,
+    /// for example `/usr/lib/weechat/plugins/irc.so`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::borrow::Cow;
+    /// # use weechat::{Weechat, ReturnCode};
+    /// # use weechat::hooks::SignalHook;
+    /// let signal_hook = SignalHook::plugin_loaded(|_: &Weechat, plugin: Cow<str>| {
+    ///     Weechat::print(&format!("Loaded plugin {}", plugin));
+    ///     ReturnCode::Ok
+    /// });
+    /// ```
+    pub fn plugin_loaded(
+        callback: impl FnMut(&Weechat, Cow<str>) -> ReturnCode + 'static,
+    ) -> Result<Self, ()> {
+        SignalHook::plugin_signal_helper("plugin_loaded", callback)
+    }
+
+    /// Hook the `plugin_unloaded` signal.
+    ///
+    /// The signal is sent out by Weechat after a plugin has been unloaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback that will be called when a plugin is
+    /// unloaded, the callback receives the name of the plugin that was
+    /// unloaded, for example `irc`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn plugin_unloaded(
+        callback: impl FnMut(&Weechat, Cow<str>) -> ReturnCode + 'static,
+    ) -> Result<Self, ()> {
+        SignalHook::plugin_signal_helper("plugin_unloaded", callback)
+    }
+
+    /// Hook the `buffer_line_added` signal for a single buffer.
+    ///
+    /// The callback will be called every time a new line is added to the
+    /// given buffer. For a single buffer this is more efficient than a global
+    /// print hook since the callback won't be called for lines of other
+    /// buffers and the line isn't converted into strings unless needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - The buffer that should be watched for new lines.
+    ///
+    /// * `callback` - The callback that will be called when a new line is
+    /// added to the buffer, the callback receives the buffer and the newly
+    /// added line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::{Weechat, ReturnCode};
+    /// # use weechat::buffer::{Buffer, BufferLine};
+    /// # use weechat::hooks::SignalHook;
+    /// # let weechat = unsafe { Weechat::weechat() };
+    /// let buffer = weechat.core_buffer();
+    ///
+    /// let signal_hook = SignalHook::buffer_line_added(
+    ///     &buffer,
+    ///     |_: &Weechat, _: &Buffer, line: BufferLine| {
+    ///         Weechat::log(&line.message());
+    ///         ReturnCode::Ok
+    ///     },
+    /// );
+    /// ```
+    pub fn buffer_line_added(
+        buffer: &Buffer,
+        mut callback: impl FnMut(&Weechat, &Buffer, BufferLine) -> ReturnCode + 'static,
+    ) -> Result<Self, ()> {
+        // The pointer is only used for comparison, it's never dereferenced.
+        let buffer_ptr = buffer.ptr();
+
+        SignalHook::new(
+            "buffer_line_added",
+            move |weechat: &Weechat, _: &str, data: Option<SignalData>| {
+                if let Some(SignalData::BufferLine(line)) = data {
+                    if line.buffer_ptr() == buffer_ptr {
+                        let buffer = weechat.buffer_from_ptr(buffer_ptr);
+                        return callback(weechat, &buffer, line);
+                    }
+                }
+
+                ReturnCode::Ok
+            },
+        )
+    }
+
+    /// Hook the `day_changed` signal.
+    ///
+    /// The signal is sent out by Weechat when the system date has changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback that will be called when the day changes,
+    /// the callback receives the new date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::{Weechat, ReturnCode};
+    /// # use weechat::hooks::{SignalDate, SignalHook};
+    /// let signal_hook = SignalHook::day_changed(|_: &Weechat, date: SignalDate| {
+    ///     Weechat::print(&format!("Rotating the log files, today is {}", date));
+    ///     ReturnCode::Ok
+    /// });
+    /// ```
+    pub fn day_changed(
+        mut callback: impl FnMut(&Weechat, SignalDate) -> ReturnCode + 'static,
+    ) -> Result<Self, ()> {
+        SignalHook::new(
+            "day_changed",
+            move |weechat: &Weechat, _: &str, data: Option<SignalData>| match data {
+                Some(SignalData::String(date)) => match SignalDate::parse(&date) {
+                    Some(date) => callback(weechat, date),
+                    None => ReturnCode::Ok,
+                },
+                _ => ReturnCode::Ok,
+            },
+        )
+    }
+
+    /// Hook the `signal_sighup` signal.
+    ///
+    /// The signal is sent out by Weechat when it receives the `SIGHUP` signal.
+    /// By default Weechat reloads its configuration files, returning
+    /// `ReturnCode::OkEat` prevents that.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback that will be called when Weechat receives
+    /// the `SIGHUP` signal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn sighup(mut callback: impl FnMut(&Weechat) -> ReturnCode + 'static) -> Result<Self, ()> {
+        SignalHook::new(
+            "signal_sighup",
+            move |weechat: &Weechat, _: &str, _: Option<SignalData>| callback(weechat),
+        )
+    }
+
+    /// Hook the `quit` signal.
+    ///
+    /// The signal is sent out by Weechat when the `/quit` command is run.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback that will be called when Weechat quits,
+    /// the callback receives the arguments of the `/quit` command, e.g. the
+    /// quit message, or an empty string if no arguments were given.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn quit(
+        mut callback: impl FnMut(&Weechat, Cow<str>) -> ReturnCode + 'static,
+    ) -> Result<Self, ()> {
+        SignalHook::new(
+            "quit",
+            move |weechat: &Weechat, _: &str, data: Option<SignalData>| match data {
+                Some(SignalData::String(arguments)) => callback(weechat, arguments),
+                _ => callback(weechat, Cow::from("")),
+            },
+        )
+    }
+
+    fn plugin_signal_helper(
+        signal_name: &str,
+        mut callback: impl FnMut(&Weechat, Cow<str>) -> ReturnCode + 'static,
+    ) -> Result<Self, ()> {
+        SignalHook::new(
+            signal_name,
+            move |weechat: &Weechat, _: &str, data: Option<SignalData>| {
+                // Both signals send out a string, anything else means that
+                // the signal wasn't sent by Weechat, ignore it.
+                if let Some(SignalData::String(plugin)) = data {
+                    callback(weechat, plugin)
+                } else {
+                    ReturnCode::Ok
+                }
+            },
+        )
+    }
+}
+
+/// Builder that dispatches the signals of a single wildcard signal hook to
+/// handlers that are registered per signal name.
+///
+/// The router installs a single `SignalHook` for the given pattern, the
+/// handlers are owned by the hook and are dropped together with it.
+///
+/// Signals are dispatched using the full signal name first. If no handler
+/// for the full name exists and the signal name contains a comma, e.g. the
+/// IRC signals which are prefixed with the server name like
+/// `libera,irc_in_privmsg`, the part after the first comma is used. Signals
+/// without a matching handler are passed to the fallback handler if one is
+/// set, otherwise they are ignored.
+///
+/// # Example
+///
+/// ```no_run
+/// # use weechat::{Weechat, ReturnCode};
+/// # use weechat::hooks::{SignalData, SignalRouter};
+/// let hook = SignalRouter::new("*,irc_in_*")
+///     .route("irc_in_privmsg", |_: &Weechat, signal: &str, _: Option<SignalData>| {
+///         Weechat::print(&format!("Got a message: {}", signal));
+///         ReturnCode::Ok
+///     })
+///     .route("irc_in_join", |_: &Weechat, signal: &str, _: Option<SignalData>| {
+///         Weechat::print(&format!("Someone joined: {}", signal));
+///         ReturnCode::Ok
+///     })
+///     .build()
+///     .expect("Can't hook the IRC signals");
+/// ```
+pub struct SignalRouter {
+    pattern: String,
+    routes: HashMap<String, Box<dyn SignalCallback>>,
+    fallback: Option<Box<dyn SignalCallback>>,
+}
+
+impl SignalRouter {
+    /// Create a new signal router.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The signal pattern that should be hooked, the wildcard
+    ///     `*` is allowed.
+    pub fn new<P: Into<String>>(pattern: P) -> Self {
+        SignalRouter {
+            pattern: pattern.into(),
+            routes: HashMap::new(),
+            fallback: None,
+        }
+    }
+
+    /// Register a handler for the given signal name.
+    ///
+    /// Registering a second handler for the same name replaces the first one.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal_name` - The exact name of the signal, or for signals that are
+    ///     prefixed with a server name the part after the comma.
+    ///
+    /// * `callback` - The callback that will be called for the signal.
+    pub fn route<N: Into<String>>(
+        mut self,
+        signal_name: N,
+        callback: impl SignalCallback + 'static,
+    ) -> Self {
+        self.routes.insert(signal_name.into(), Box::new(callback));
+        self
+    }
+
+    /// Set a handler for signals that don't have a registered handler.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback that will be called for unrouted signals.
+    pub fn fallback(mut self, callback: impl SignalCallback + 'static) -> Self {
+        self.fallback = Some(Box::new(callback));
+        self
+    }
+
+    /// Install the signal hook.
+    ///
+    /// Returns the hook that owns all the handlers, the signals stop being
+    /// dispatched once the hook is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn build(self) -> Result<SignalHook, ()> {
+        let SignalRouter {
+            pattern,
+            mut routes,
+            mut fallback,
+        } = self;
+
+        SignalHook::new(
+            &pattern,
+            move |weechat: &Weechat, signal_name: &str, data: Option<SignalData>| {
+                match SignalRouter::select_route(&mut routes, &mut fallback, signal_name) {
+                    Some(callback) => callback.callback(weechat, signal_name, data),
+                    None => ReturnCode::Ok,
+                }
+            },
+        )
+    }
+
+    /// Find the handler for the given signal name.
+    ///
+    /// The full signal name is tried first, then the part after the first
+    /// comma and finally the fallback.
+    fn select_route<'r, V>(
+        routes: &'r mut HashMap<String, V>,
+        fallback: &'r mut Option<V>,
+        signal_name: &str,
+    ) -> Option<&'r mut V> {
+        if routes.contains_key(signal_name) {
+            return routes.get_mut(signal_name);
+        }
+
+        let suffix = signal_name
+            .splitn(2, ',')
+            .nth(1)
+            .filter(|name| routes.contains_key(*name));
+
+        match suffix {
+            Some(name) => routes.get_mut(name),
+            None => fallback.as_mut(),
+        }
+    }
 }
 
 impl Weechat {
@@ -339,14 +750,27 @@ impl Weechat {
             }
         } else {
             let (ptr, data_type) = match data {
-                SignalData::Integer(number) => (
-                    number as *mut _,
+                // Weechat expects a pointer to the integer, the integer
+                // lives until the signal is sent out.
+                SignalData::Integer(ref number) => (
+                    number as *const c_int as *mut c_void,
                     weechat_sys::WEECHAT_HOOK_SIGNAL_INT as *const u8,
                 ),
                 SignalData::Buffer(buffer) => (
                     buffer.ptr() as *mut _,
                     weechat_sys::WEECHAT_HOOK_SIGNAL_POINTER as *const u8,
                 ),
+                SignalData::BufferLine(line) => (
+                    line.line_ptr(),
+                    weechat_sys::WEECHAT_HOOK_SIGNAL_POINTER as *const u8,
+                ),
+                SignalData::Window(window) => (
+                    window.ptr as *mut _,
+                    weechat_sys::WEECHAT_HOOK_SIGNAL_POINTER as *const u8,
+                ),
+                SignalData::Pointer(ptr) => {
+                    (ptr, weechat_sys::WEECHAT_HOOK_SIGNAL_POINTER as *const u8)
+                }
                 SignalData::String(_) => unreachable!(),
             };
             unsafe { signal_send(signal_name.as_ptr(), data_type as *const c_char, ptr) }
@@ -359,4 +783,143 @@ impl Weechat {
             _ => ReturnCode::Error,
         }
     }
+
+    /// Send a signal with string data.
+    ///
+    /// This is a typed shorthand for `hook_signal_send()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal_name` - The name of the signal that should be sent out.
+    ///
+    /// * `data` - The string that should be sent with the signal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn send_signal_string(signal_name: &str, data: &str) -> ReturnCode {
+        Weechat::hook_signal_send(signal_name, data)
+    }
+
+    /// Send a signal with integer data.
+    ///
+    /// This is a typed shorthand for `hook_signal_send()`, receivers get a
+    /// pointer to the integer as Weechat expects it.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal_name` - The name of the signal that should be sent out.
+    ///
+    /// * `data` - The integer that should be sent with the signal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn send_signal_int(signal_name: &str, data: i32) -> ReturnCode {
+        Weechat::hook_signal_send(signal_name, data)
+    }
+
+    /// Send a signal with a buffer pointer as data.
+    ///
+    /// This is a typed shorthand for `hook_signal_send()`, the raw pointer of
+    /// the buffer is sent as the signal data, the same way Weechat sends
+    /// buffer signals, e.g. `buffer_switch`.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal_name` - The name of the signal that should be sent out.
+    ///
+    /// * `buffer` - The buffer that should be sent with the signal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// # let weechat = unsafe { Weechat::weechat() };
+    /// let buffer = weechat.core_buffer();
+    ///
+    /// // Ask the logger plugin to print the backlog of the buffer.
+    /// Weechat::send_signal_buffer("logger_backlog", &buffer);
+    /// ```
+    pub fn send_signal_buffer(signal_name: &str, buffer: &Buffer) -> ReturnCode {
+        Weechat::hook_signal_send(signal_name, buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn routes() -> HashMap<String, &'static str> {
+        let mut routes = HashMap::new();
+        routes.insert("irc_in_privmsg".to_owned(), "privmsg");
+        routes.insert("irc_in_join".to_owned(), "join");
+        routes.insert("buffer_switch".to_owned(), "switch");
+        routes
+    }
+
+    #[test]
+    fn router_dispatches_two_signals_of_one_wildcard() {
+        let mut routes = routes();
+        let mut fallback = None;
+
+        assert_eq!(
+            SignalRouter::select_route(&mut routes, &mut fallback, "libera,irc_in_privmsg"),
+            Some(&mut "privmsg")
+        );
+        assert_eq!(
+            SignalRouter::select_route(&mut routes, &mut fallback, "libera,irc_in_join"),
+            Some(&mut "join")
+        );
+    }
+
+    #[test]
+    fn router_prefers_the_exact_name() {
+        let mut routes = routes();
+        routes.insert("libera,irc_in_join".to_owned(), "libera join");
+        let mut fallback = None;
+
+        assert_eq!(
+            SignalRouter::select_route(&mut routes, &mut fallback, "buffer_switch"),
+            Some(&mut "switch")
+        );
+        assert_eq!(
+            SignalRouter::select_route(&mut routes, &mut fallback, "libera,irc_in_join"),
+            Some(&mut "libera join")
+        );
+    }
+
+    #[test]
+    fn router_uses_the_fallback() {
+        let mut routes = routes();
+        let mut fallback = Some("fallback");
+
+        assert_eq!(
+            SignalRouter::select_route(&mut routes, &mut fallback, "libera,irc_in_part"),
+            Some(&mut "fallback")
+        );
+        assert_eq!(
+            SignalRouter::select_route(&mut routes, &mut fallback, "irc_in_privmsg,"),
+            Some(&mut "fallback")
+        );
+    }
+
+    #[test]
+    fn router_ignores_signals_without_handler() {
+        let mut routes = routes();
+        let mut fallback = None;
+
+        assert_eq!(
+            SignalRouter::select_route(&mut routes, &mut fallback, "libera,irc_in_part"),
+            None
+        );
+        assert_eq!(
+            SignalRouter::select_route(&mut routes, &mut fallback, "irc_in"),
+            None
+        );
+    }
 }
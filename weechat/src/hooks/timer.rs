@@ -6,6 +6,14 @@ use weechat_sys::{t_weechat_plugin, WEECHAT_RC_OK};
 use super::Hook;
 use crate::Weechat;
 
+#[cfg(feature = "async")]
+use futures::{
+    stream::Stream,
+    task::{Context, Poll, Waker},
+};
+#[cfg(feature = "async")]
+use std::{cell::RefCell, pin::Pin, rc::Rc};
+
 /// A hook for a timer, the hook will be removed when the object is dropped.
 pub struct TimerHook {
     _hook: Hook,
@@ -16,7 +24,8 @@ pub struct TimerHook {
 pub enum RemainingCalls {
     /// Infinitely many remaining calls.
     Infinite,
-    /// A finite number of calls is remaining.
+    /// A finite number of calls is remaining, the last call of the timer
+    /// receives `Finite(0)`.
     Finite(i32),
 }
 
@@ -58,20 +67,31 @@ struct TimerHookData {
 impl TimerHook {
     /// Create a timer that will repeatedly fire.
     ///
+    /// The timer is removed when the hook is dropped, the callback won't be
+    /// called after that. Weechat can't fire a timer before its interval
+    /// elapses, if the work of the timer needs to be done early, e.g. when
+    /// the plugin is unloaded, keep the state of the timer outside of the
+    /// callback so it can be accessed from elsewhere, see the debounce example
+    /// below.
+    ///
     /// # Arguments
     ///
-    /// * `interval` - The delay between calls in milliseconds.
+    /// * `interval` - The delay between calls. Weechat timers have a
+    ///     granularity of one millisecond, the duration is truncated to whole
+    ///     milliseconds. An interval shorter than one millisecond is rejected
+    ///     by Weechat and an error is returned.
     ///
-    /// * `align_second` - The alignment on a second. For example, if the
-    ///     current time is 09:00, if the interval = 60000 (60 seconds), and
-    ///     align_second = 60, then timer is called each minute on the 0th
-    ///     second.
+    /// * `align_second` - The alignment on a second, 0 disables the
+    ///     alignment. For example, if the current time is 09:00, the interval
+    ///     is 60 seconds and align_second is 60, then the timer is called each
+    ///     minute on the 0th second.
     ///
     /// * `max_calls` - The number of times the callback should be called, 0
     ///     means it's called forever.
     ///
     /// * `callback` - A function that will be called when the timer fires, the
-    ///     `remaining` argument will be -1 if the timer has no end.
+    ///     `remaining` argument will be `RemainingCalls::Infinite` if the timer
+    ///     has no end.
     ///
     /// # Panics
     ///
@@ -84,11 +104,64 @@ impl TimerHook {
     /// # use weechat::hooks::{TimerHook, RemainingCalls};
     ///
     /// let timer = TimerHook::new(
-    ///     Duration::from_secs(1), 0, -1,
+    ///     Duration::from_secs(1), 0, 0,
     ///     |_: &Weechat, _: RemainingCalls| {
     ///         Weechat::print("Running timer hook");
     ///     }
     /// ).expect("Can't create timer hook");
+    ///
+    /// // A clock that fires at the start of every minute, e.g. to update a
+    /// // bar item.
+    /// let clock = TimerHook::new(
+    ///     Duration::from_secs(60), 60, 0,
+    ///     |_: &Weechat, _: RemainingCalls| {
+    ///         Weechat::bar_item_update("my_clock");
+    ///     }
+    /// ).expect("Can't create timer hook");
+    ///
+    /// // A countdown that cleans up after the last call, the callback receives
+    /// // 2, 1 and 0 as the remaining calls.
+    /// let countdown = TimerHook::new(
+    ///     Duration::from_secs(1), 0, 3,
+    ///     |_: &Weechat, remaining: RemainingCalls| {
+    ///         match remaining {
+    ///             RemainingCalls::Finite(0) => Weechat::print("Liftoff"),
+    ///             RemainingCalls::Finite(n) => Weechat::print(&n.to_string()),
+    ///             RemainingCalls::Infinite => (),
+    ///         }
+    ///     }
+    /// ).expect("Can't create timer hook");
+    ///
+    /// // A debounce that saves the state after 5 seconds of inactivity, the
+    /// // state can be flushed immediately without waiting for the timer.
+    /// use std::{cell::RefCell, rc::Rc};
+    ///
+    /// #[derive(Default)]
+    /// struct State {
+    ///     dirty: bool,
+    /// }
+    ///
+    /// fn flush(state: &RefCell<State>) {
+    ///     let mut state = state.borrow_mut();
+    ///
+    ///     if state.dirty {
+    ///         Weechat::print("Saving state");
+    ///         state.dirty = false;
+    ///     }
+    /// }
+    ///
+    /// let state = Rc::new(RefCell::new(State::default()));
+    /// state.borrow_mut().dirty = true;
+    ///
+    /// let timer_state = state.clone();
+    /// let debounce = TimerHook::new(
+    ///     Duration::from_secs(5), 0, 1,
+    ///     move |_: &Weechat, _: RemainingCalls| flush(&timer_state),
+    /// ).expect("Can't create timer hook");
+    ///
+    /// // On unload, remove the timer and flush right away.
+    /// drop(debounce);
+    /// flush(&state);
     /// ```
     pub fn new(
         interval: Duration,
@@ -149,3 +222,112 @@ impl TimerHook {
         }
     }
 }
+
+#[cfg(feature = "async")]
+#[derive(Default)]
+struct IntervalState {
+    ticked: bool,
+    waker: Option<Waker>,
+}
+
+/// A stream that yields every time the interval elapses, created by
+/// `Weechat::interval()`.
+///
+/// The underlying timer is removed when the stream is dropped.
+#[cfg(feature = "async")]
+#[cfg_attr(feature = "docs", doc(cfg(r#async)))]
+pub struct IntervalStream {
+    _hook: TimerHook,
+    state: Rc<RefCell<IntervalState>>,
+}
+
+#[cfg(feature = "async")]
+impl Stream for IntervalStream {
+    type Item = ();
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.state.borrow_mut();
+
+        if state.ticked {
+            state.ticked = false;
+            Poll::Ready(Some(()))
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(feature = "docs", doc(cfg(r#async)))]
+impl Weechat {
+    /// Create a stream that yields every time the given interval elapses.
+    ///
+    /// Ticks that happen while the previous tick wasn't consumed yet are
+    /// coalesced, a slow task gets a single item instead of a backlog of
+    /// ticks.
+    ///
+    /// Returns an error if the timer couldn't be created, e.g. if the interval
+    /// is zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The time between ticks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use futures::StreamExt;
+    /// # use weechat::Weechat;
+    /// Weechat::spawn(async {
+    ///     let mut interval = Weechat::interval(Duration::from_secs(5))
+    ///         .expect("Can't create interval");
+    ///
+    ///     while let Some(_) = interval.next().await {
+    ///         Weechat::print("Refreshing");
+    ///     }
+    /// })
+    /// .detach();
+    /// ```
+    pub fn interval(interval: Duration) -> Result<IntervalStream, ()> {
+        let state = Rc::new(RefCell::new(IntervalState::default()));
+        let timer_state = Rc::downgrade(&state);
+
+        let hook = TimerHook::new(interval, 0, 0, move |_: &Weechat, _: RemainingCalls| {
+            if let Some(state) = timer_state.upgrade() {
+                let mut state = state.borrow_mut();
+                state.ticked = true;
+
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            }
+        })?;
+
+        Ok(IntervalStream { _hook: hook, state })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaining_calls_count_down() {
+        for remaining in [2, 1, 0].iter() {
+            assert!(matches!(
+                RemainingCalls::from(*remaining),
+                RemainingCalls::Finite(r) if r == *remaining
+            ));
+        }
+    }
+
+    #[test]
+    fn infinite_remaining_calls() {
+        assert!(matches!(RemainingCalls::from(-1), RemainingCalls::Infinite));
+    }
+}
@@ -3,7 +3,31 @@
 //! The list of available infolists can be found in the Weechat plugin API
 //! reference.
 //!
-//! # Example
+//! The variables of an infolist item are typed by Weechat, they map to
+//! `InfolistVariable` values like this:
+//!
+//! * integer variables are returned as `InfolistVariable::Integer`.
+//! * string variables are returned as `InfolistVariable::String`.
+//! * time variables are returned as `InfolistVariable::Time`.
+//! * pointer variables are only returned if they are known to point to a
+//!     buffer, e.g. the `pointer` variable of the `buffer` infolist or the
+//!     `buffer` variable of the `window` infolist, those are returned as
+//!     `InfolistVariable::Buffer`. Other pointers are skipped.
+//! * buffer variables, opaque chunks of binary data, are skipped.
+//!
+//! # Item validity
+//!
+//! An infolist has a single cursor, an `InfolistItem` always reads the
+//! variables of the item the cursor currently points to. Once the infolist
+//! advances to the next item, e.g. by the next iteration of a `for` loop, a
+//! previously yielded item reads the variables of the new current item. Read
+//! the variables you need, or convert them into owned values, before
+//! advancing the infolist.
+//!
+//! Strings that are returned by an item borrow memory of the infolist and
+//! must not be used after the infolist is dropped. Buffers that are returned
+//! by an item are regular Weechat buffers, they don't depend on the infolist
+//! and stay valid until the buffer is closed.
 //!
 //! # Examples
 //! ```no_run
@@ -25,7 +49,7 @@ use std::{
         hash_map::{IntoIter as IterHashmap, Keys},
         HashMap,
     },
-    ffi::CStr,
+    ffi::{c_void, CStr},
     fmt::Debug,
     marker::PhantomData,
     ptr,
@@ -46,6 +70,7 @@ use crate::{
 pub struct Infolist<'a> {
     ptr: *mut t_infolist,
     infolist_name: String,
+    position: usize,
     weechat: &'a Weechat,
 }
 
@@ -71,6 +96,64 @@ impl From<&str> for InfolistType {
     }
 }
 
+/// The raw type of an infolist field, as reported by Weechat.
+#[derive(Eq, Hash, Debug, PartialEq, PartialOrd, Ord, Clone, Copy)]
+pub enum InfolistFieldType {
+    /// An integer field.
+    Integer,
+    /// A string field.
+    String,
+    /// A pointer field, this may point to a GUI buffer or any other object.
+    Pointer,
+    /// A buffer field, a chunk of binary data of arbitrary size.
+    Buffer,
+    /// A time field.
+    Time,
+}
+
+impl InfolistFieldType {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "i" => Some(InfolistFieldType::Integer),
+            "s" => Some(InfolistFieldType::String),
+            "p" => Some(InfolistFieldType::Pointer),
+            "b" => Some(InfolistFieldType::Buffer),
+            "t" => Some(InfolistFieldType::Time),
+            _ => None,
+        }
+    }
+}
+
+/// Get the raw fields of the current item of an infolist.
+fn infolist_fields(
+    weechat: &Weechat,
+    infolist: *mut t_infolist,
+) -> Vec<(String, InfolistFieldType)> {
+    let infolist_fields = weechat.get().infolist_fields.unwrap();
+
+    let fields_string = unsafe {
+        let ptr = infolist_fields(infolist);
+
+        if ptr.is_null() {
+            return Vec::new();
+        }
+
+        CStr::from_ptr(ptr).to_string_lossy()
+    };
+
+    fields_string
+        .split(',')
+        .filter_map(|field| {
+            let mut split = field.splitn(2, ':');
+
+            let field_type = InfolistFieldType::parse(split.next()?)?;
+            let name = split.next()?;
+
+            Some((name.to_owned(), field_type))
+        })
+        .collect()
+}
+
 /// An item of the infolist.
 ///
 /// Each infolist item may contain multiple values. It essentially acts as a
@@ -163,6 +246,42 @@ impl<'a> InfolistItem<'a> {
         Some(variable)
     }
 
+    /// Get a string variable from the current infolist item.
+    ///
+    /// Returns `None` if the item doesn't contain a string variable with the
+    /// given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The name of the variable that should be fetched.
+    pub fn get_string(&self, key: &str) -> Option<Cow<str>> {
+        match self.fields.get(key)? {
+            InfolistType::String => self.string(key),
+            _ => None,
+        }
+    }
+
+    /// Get all the fields of the item, including the ones that can't be
+    /// fetched using `get()`, together with their raw Weechat types.
+    ///
+    /// The fields may differ between the items of an infolist.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// # let weechat = unsafe { weechat::Weechat::weechat() };
+    /// let infolist = weechat.get_infolist("hook", Some("infolist")).unwrap();
+    ///
+    /// for item in infolist {
+    ///     for (name, field_type) in item.fields() {
+    ///         Weechat::print(&format!("{}: {:?}", name, field_type));
+    ///     }
+    /// }
+    /// ```
+    pub fn fields(&self) -> Vec<(String, InfolistFieldType)> {
+        infolist_fields(&self.weechat, self.ptr)
+    }
+
     /// Get the list of infolist variables that this item has.
     pub fn keys(&self) -> Keys<'_, String, InfolistType> {
         self.fields.keys()
@@ -242,7 +361,85 @@ pub enum InfolistVariable<'a> {
     Buffer(Buffer<'a>),
 }
 
+impl<'a> InfolistVariable<'a> {
+    /// Get the value of the variable if it's a string variable.
+    pub fn as_string(&self) -> Option<Cow<str>> {
+        if let InfolistVariable::String(s) = self {
+            Some(Cow::Borrowed(s.as_ref()))
+        } else {
+            None
+        }
+    }
+
+    /// Get the value of the variable if it's an integer variable.
+    pub fn as_integer(&self) -> Option<i32> {
+        if let InfolistVariable::Integer(i) = self {
+            Some(*i)
+        } else {
+            None
+        }
+    }
+
+    /// Get the value of the variable if it's a time variable.
+    pub fn as_time(&self) -> Option<SystemTime> {
+        if let InfolistVariable::Time(t) = self {
+            Some(*t)
+        } else {
+            None
+        }
+    }
+
+    /// Get the value of the variable if it's a buffer variable.
+    pub fn as_buffer(&self) -> Option<&Buffer<'a>> {
+        if let InfolistVariable::Buffer(b) = self {
+            Some(b)
+        } else {
+            None
+        }
+    }
+}
+
 impl<'a> Infolist<'a> {
+    /// Get the number of items the infolist contains.
+    ///
+    /// This doesn't consume the infolist, the position of the infolist is
+    /// preserved.
+    ///
+    /// Note that the infolist is also an iterator, the `count()` method of the
+    /// `Iterator` trait consumes the infolist and only counts the items that
+    /// weren't yet yielded.
+    pub fn item_count(&self) -> usize {
+        let infolist_next = self.weechat.get().infolist_next.unwrap();
+        let infolist_reset_item_cursor = self.weechat.get().infolist_reset_item_cursor.unwrap();
+
+        unsafe {
+            infolist_reset_item_cursor(self.ptr);
+
+            let mut count = 0;
+
+            while infolist_next(self.ptr) == 1 {
+                count += 1;
+            }
+
+            infolist_reset_item_cursor(self.ptr);
+
+            for _ in 0..self.position {
+                infolist_next(self.ptr);
+            }
+
+            count
+        }
+    }
+
+    /// Rewind the infolist, the next item that is yielded will be the first
+    /// item of the infolist.
+    pub fn reset(&mut self) {
+        let infolist_reset_item_cursor = self.weechat.get().infolist_reset_item_cursor.unwrap();
+
+        unsafe { infolist_reset_item_cursor(self.ptr) };
+        self.position = 0;
+    }
+
     fn is_pointer_buffer(infolist_name: &str, variable_name: &str) -> bool {
         matches!(
             (infolist_name, variable_name),
@@ -256,38 +453,27 @@ impl<'a> Infolist<'a> {
     }
 
     fn get_fields(&self) -> HashMap<String, InfolistType> {
-        let infolist_fields = self.weechat.get().infolist_fields.unwrap();
         let mut fields: HashMap<String, InfolistType> = HashMap::new();
 
-        let fields_string = unsafe {
-            let ptr = infolist_fields(self.ptr);
-            CStr::from_ptr(ptr).to_string_lossy()
-        };
-
-        for field in fields_string.split(',') {
-            let split: Vec<&str> = field.split(':').collect();
-
-            let infolist_type = split[0];
-            let name = split[1];
-
-            // Skip the buffer, we can't safely expose them
-            // without knowing the size of the buffer. (Note the buffer here
-            // isn't a GUI buffer but a vector like thing.
-            if infolist_type == "b" {
-                continue;
-            }
-
-            let field = if infolist_type == "p" {
-                if Infolist::is_pointer_buffer(&self.infolist_name, name) {
-                    InfolistType::Buffer
-                } else {
-                    continue;
+        for (name, field_type) in infolist_fields(self.weechat, self.ptr) {
+            let field = match field_type {
+                InfolistFieldType::Integer => InfolistType::Integer,
+                InfolistFieldType::String => InfolistType::String,
+                InfolistFieldType::Time => InfolistType::Time,
+                // Skip the buffer, we can't safely expose them
+                // without knowing the size of the buffer. (Note the buffer here
+                // isn't a GUI buffer but a vector like thing.
+                InfolistFieldType::Buffer => continue,
+                InfolistFieldType::Pointer => {
+                    if Infolist::is_pointer_buffer(&self.infolist_name, &name) {
+                        InfolistType::Buffer
+                    } else {
+                        continue;
+                    }
                 }
-            } else {
-                InfolistType::from(infolist_type)
             };
 
-            fields.insert(name.to_owned(), field);
+            fields.insert(name, field);
         }
 
         fields
@@ -338,6 +524,17 @@ impl Weechat {
         &self,
         infolist_name: &str,
         arguments: Option<&str>,
+    ) -> Result<Infolist, ()> {
+        self.get_infolist_with_pointer(infolist_name, ptr::null_mut(), arguments)
+    }
+
+    /// Get the infolist with the given name for a specific object, e.g. the
+    /// lines of a buffer.
+    pub(crate) fn get_infolist_with_pointer(
+        &self,
+        infolist_name: &str,
+        pointer: *mut c_void,
+        arguments: Option<&str>,
     ) -> Result<Infolist, ()> {
         let infolist_get = self.get().infolist_get.unwrap();
 
@@ -352,7 +549,7 @@ impl Weechat {
             infolist_get(
                 self.ptr,
                 name.as_ptr(),
-                ptr::null_mut(),
+                pointer,
                 arguments.map_or(ptr::null_mut(), |a| a.as_ptr()),
             )
         };
@@ -363,6 +560,7 @@ impl Weechat {
             Ok(Infolist {
                 ptr: infolist_ptr,
                 infolist_name: infolist_name.to_owned(),
+                position: 0,
                 weechat: &self,
             })
         }
@@ -378,6 +576,7 @@ impl<'a> Iterator for Infolist<'a> {
         let ret = unsafe { infolist_next(self.ptr) };
 
         if ret == 1 {
+            self.position += 1;
             let fields = self.get_fields();
 
             Some(InfolistItem {
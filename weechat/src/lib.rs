@@ -44,7 +44,6 @@ use std::ffi::CString;
 #[cfg(feature = "async")]
 mod executor;
 mod hashtable;
-mod hdata;
 mod weechat;
 
 #[cfg(feature = "config_macro")]
@@ -56,12 +55,14 @@ pub use paste;
 #[cfg(feature = "config_macro")]
 pub use strum;
 
+pub mod bar;
 pub mod buffer;
 pub mod config;
+pub mod hdata;
 pub mod hooks;
 pub mod infolist;
 
-pub use crate::weechat::{Args, Prefix, Weechat};
+pub use crate::weechat::{Args, ColorCodeKind, Prefix, SplitFlags, Weechat};
 
 pub use libc;
 pub use weechat_macro::plugin;
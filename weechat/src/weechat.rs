@@ -7,6 +7,7 @@ use weechat_sys::t_weechat_plugin;
 use crate::LossyCString;
 use libc::{c_char, c_int};
 use std::{
+    collections::HashMap,
     ffi::{CStr, CString},
     panic::PanicInfo,
     path::PathBuf,
@@ -24,6 +25,7 @@ use std::future::Future;
 /// for each argument.
 pub struct Args {
     iter: vec::IntoIter<String>,
+    lines: Vec<String>,
 }
 
 /// A Weechat prefix, can be prepended to a message to notify the message
@@ -53,6 +55,86 @@ impl Prefix {
     }
 }
 
+const COLOR: u8 = 0x19;
+const SET_ATTR: u8 = 0x1A;
+const REMOVE_ATTR: u8 = 0x1B;
+const RESET: u8 = 0x1C;
+
+/// The kinds of color codes a string with Weechat colors can contain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorCodeKind {
+    /// A code that sets a foreground or background color, or both.
+    Color,
+    /// A code that enables an attribute, e.g. bold.
+    SetAttribute,
+    /// A code that disables an attribute.
+    RemoveAttribute,
+    /// A code that resets the color and all the attributes.
+    Reset,
+}
+
+impl ColorCodeKind {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            COLOR => Some(ColorCodeKind::Color),
+            SET_ATTR => Some(ColorCodeKind::SetAttribute),
+            REMOVE_ATTR => Some(ColorCodeKind::RemoveAttribute),
+            RESET => Some(ColorCodeKind::Reset),
+            _ => None,
+        }
+    }
+}
+
+/// Flags that control how `Weechat::string_split()` splits a string.
+///
+/// The default flags don't modify the string and keep empty items between
+/// consecutive separators.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SplitFlags {
+    /// Strip separators at the start of the string before splitting it.
+    pub strip_left: bool,
+    /// Strip separators at the end of the string before splitting it.
+    pub strip_right: bool,
+    /// Treat consecutive separators as a single separator, no empty items are
+    /// created.
+    pub collapse_separators: bool,
+    /// Every item contains the rest of the string, up to the end of the
+    /// string, instead of only the part until the next separator.
+    pub keep_eol: bool,
+}
+
+impl SplitFlags {
+    /// The flags Weechat uses for most of its lists, separators are stripped
+    /// on both sides and consecutive separators are collapsed.
+    pub fn strip_and_collapse() -> Self {
+        SplitFlags {
+            strip_left: true,
+            strip_right: true,
+            collapse_separators: true,
+            keep_eol: false,
+        }
+    }
+
+    fn bits(&self) -> c_int {
+        let mut flags = 0;
+
+        if self.strip_left {
+            flags |= weechat_sys::WEECHAT_STRING_SPLIT_STRIP_LEFT;
+        }
+        if self.strip_right {
+            flags |= weechat_sys::WEECHAT_STRING_SPLIT_STRIP_RIGHT;
+        }
+        if self.collapse_separators {
+            flags |= weechat_sys::WEECHAT_STRING_SPLIT_COLLAPSE_SEPS;
+        }
+        if self.keep_eol {
+            flags |= weechat_sys::WEECHAT_STRING_SPLIT_KEEP_EOL;
+        }
+
+        flags
+    }
+}
+
 impl Args {
     /// Create an Args object from the underlying weechat C types.
     /// Expects the strings in argv to be valid utf8, if not invalid UTF-8
@@ -65,16 +147,67 @@ impl Args {
     /// plugin init method.
     #[doc(hidden)]
     pub fn new(argc: c_int, argv: *mut *mut c_char) -> Args {
-        let argc = argc as isize;
-        let args: Vec<String> = (0..argc)
+        Args {
+            iter: Args::to_vec(argc, argv).into_iter(),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Create an Args object from the arguments of a command callback.
+    ///
+    /// The `argv_eol` array contains, for every argument, the rest of the
+    /// command line starting at that argument.
+    pub(crate) fn from_command(
+        argc: c_int,
+        argv: *mut *mut c_char,
+        argv_eol: *mut *mut c_char,
+    ) -> Args {
+        let lines = if argv_eol.is_null() {
+            Vec::new()
+        } else {
+            Args::to_vec(argc, argv_eol)
+        };
+
+        Args {
+            iter: Args::to_vec(argc, argv).into_iter(),
+            lines,
+        }
+    }
+
+    fn to_vec(argc: c_int, argv: *mut *mut c_char) -> Vec<String> {
+        (0..argc as isize)
             .map(|i| {
                 let cstr = unsafe { CStr::from_ptr(*argv.offset(i) as *const libc::c_char) };
 
                 String::from_utf8_lossy(&cstr.to_bytes().to_vec()).to_string()
             })
-            .collect();
-        Args {
-            iter: args.into_iter(),
+            .collect()
+    }
+
+    /// Split the remaining arguments like a shell would do it.
+    ///
+    /// Arguments can be quoted using single or double quotes and spaces can
+    /// be escaped using a backslash, e.g. `"hello world" it\'s\ me` results
+    /// in the arguments `hello world` and `it's me`.
+    ///
+    /// For the arguments of a command the original command line is split,
+    /// otherwise the remaining arguments are joined with a single space
+    /// before they are split again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn split_quoted(self) -> Vec<String> {
+        Weechat::string_split_shell(&self.remaining_line())
+    }
+
+    /// Get the part of the command line that wasn't consumed yet.
+    fn remaining_line(self) -> String {
+        let consumed = self.lines.len().saturating_sub(self.iter.len());
+
+        match self.lines.get(consumed) {
+            Some(line) => line.clone(),
+            _ => self.iter.collect::<Vec<String>>().join(" "),
         }
     }
 }
@@ -111,6 +244,28 @@ impl DoubleEndedIterator for Args {
 
 static mut WEECHAT: Option<Weechat> = None;
 static mut WEECHAT_THREAD_ID: Option<std::thread::ThreadId> = None;
+static mut INFO_CACHE: Option<HashMap<String, Option<String>>> = None;
+
+/// Infos that don't change while Weechat is running and can be cached.
+const CONSTANT_INFOS: &[&str] = &[
+    "version",
+    "version_number",
+    "version_git",
+    "date",
+    "pid",
+    "weechat_site",
+    "weechat_site_download",
+    "weechat_dir",
+    "weechat_config_dir",
+    "weechat_data_dir",
+    "weechat_cache_dir",
+    "weechat_runtime_dir",
+    "weechat_libdir",
+    "weechat_sharedir",
+    "weechat_localedir",
+    "charset_internal",
+    "charset_terminal",
+];
 
 /// Main Weechat struct that encapsulates common weechat API functions.
 /// It has a similar API as the weechat script API.
@@ -228,6 +383,23 @@ impl Weechat {
         WeechatExecutor::free();
     }
 
+    /// Copy a string that was allocated by Weechat and free it.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be null or point to a malloc'ed C string that isn't
+    /// used afterwards.
+    unsafe fn take_string(ptr: *mut c_char) -> String {
+        if ptr.is_null() {
+            return String::new();
+        }
+
+        let string = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+        libc::free(ptr as *mut libc::c_void);
+
+        string
+    }
+
     pub(crate) fn from_ptr(ptr: *mut t_weechat_plugin) -> Weechat {
         assert!(!ptr.is_null());
         Weechat { ptr }
@@ -358,6 +530,64 @@ impl Weechat {
         Weechat::color(&format!("{},{}", foreground_color, background_color)).to_string()
     }
 
+    /// Return a string color code for one of the 256 terminal colors.
+    ///
+    /// An empty string is returned if the terminal doesn't support the given
+    /// color.
+    ///
+    /// # Arguments
+    ///
+    /// `code` - The number of the color in the 256 color palette.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn color_ansi(code: u8) -> String {
+        Weechat::color(&code.to_string()).to_string()
+    }
+
+    /// Color the characters at the given indices of a string.
+    ///
+    /// This is useful to highlight the matched characters of a fuzzy match.
+    /// Consecutive characters that share a color are grouped under a single
+    /// color code.
+    ///
+    /// # Arguments
+    ///
+    /// `text` - The string that should be colored.
+    ///
+    /// `indices` - The indices of the characters, not bytes, that should be
+    ///     highlighted.
+    ///
+    /// `highlight_color` - The color code, e.g. one returned by
+    ///     `Weechat::color()`, for the highlighted characters.
+    ///
+    /// `normal_color` - The color code for the rest of the characters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// let highlight = Weechat::color_pair("black", "red");
+    /// let normal = Weechat::color_pair("black", "cyan");
+    ///
+    /// let colored = Weechat::colorize_indices("weechat", &[0, 3], &highlight, &normal);
+    /// Weechat::print(&colored);
+    /// ```
+    pub fn colorize_indices(
+        text: &str,
+        indices: &[usize],
+        highlight_color: &str,
+        normal_color: &str,
+    ) -> String {
+        let reset = Weechat::color("reset");
+        colorize_spans(text, indices, highlight_color, normal_color, reset)
+    }
+
     /// Retrieve a prefix value
     ///
     /// # Arguments:
@@ -417,190 +647,1161 @@ impl Weechat {
         }
     }
 
-    /// Remove WeeChat colors from a string.
+    /// Get some info from Weechat that doesn't change while Weechat is
+    /// running.
+    ///
+    /// The info is fetched from Weechat only once and cached afterwards. This
+    /// avoids repeated calls into Weechat for infos that are needed often,
+    /// e.g. in loops.
+    ///
+    /// Only infos without arguments that stay constant for the whole session
+    /// are cached:
+    ///
+    /// * `version`, `version_number`, `version_git` and `date`
+    /// * `pid`
+    /// * `weechat_site` and `weechat_site_download`
+    /// * `weechat_dir`, `weechat_config_dir`, `weechat_data_dir`,
+    ///     `weechat_cache_dir` and `weechat_runtime_dir`
+    /// * `weechat_libdir`, `weechat_sharedir` and `weechat_localedir`
+    /// * `charset_internal` and `charset_terminal`
+    ///
+    /// Other infos are fetched every time, just like with `info_get()`.
     ///
     /// # Arguments
     ///
-    /// * `string` - The string that should be stripped from Weechat colors.
+    /// * `name` - name the info
     ///
     /// # Panics
     ///
     /// Panics if the method is not called from the main Weechat thread.
-    pub fn remove_color(string: &str) -> String {
+    pub fn info_get_cached(name: &str) -> Option<String> {
         Weechat::check_thread();
-        let weechat = unsafe { Weechat::weechat() };
-
-        let string = LossyCString::new(string);
 
-        let remove_color = weechat.get().string_remove_color.unwrap();
+        if !CONSTANT_INFOS.contains(&name) {
+            return Weechat::info_get(name, "");
+        }
 
-        let string = unsafe {
-            let ptr = remove_color(string.as_ptr(), ptr::null());
-            CString::from_raw(ptr)
-        };
+        // The cache is only accessed from the main Weechat thread.
+        let cache = unsafe { INFO_CACHE.get_or_insert_with(HashMap::new) };
 
-        string.to_string_lossy().to_string()
+        cache
+            .entry(name.to_owned())
+            .or_insert_with(|| Weechat::info_get(name, ""))
+            .clone()
     }
 
-    /// Evaluate a Weechat expression and return the result.
+    /// Get some info from Weechat or a plugin in the form of a hashtable.
+    ///
+    /// Hashtable based infos are, for example, `irc_message_parse` and
+    /// `irc_message_split` from the IRC plugin, or `focus_info` and
+    /// `secured_data` from Weechat itself.
     ///
     /// # Arguments
     ///
-    /// * `expression` - The expression that should be evaluated.
+    /// * `name` - name the info
+    ///
+    /// * `input` - the input hashtable for the info, the keys depend on the
+    ///     info.
+    ///
+    /// Returns None if the info doesn't exist or no info could be returned.
     ///
     /// # Panics
     ///
     /// Panics if the method is not called from the main Weechat thread.
-    //
-    // TODO: Add hashtable options
-    // TODO: This needs better docs and examples.
-    pub fn eval_string_expression(expression: &str) -> Result<String, ()> {
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use weechat::Weechat;
+    /// let mut input = HashMap::new();
+    /// input.insert("message", ":nick!user@host PRIVMSG #weechat :hello");
+    ///
+    /// if let Some(parsed) = Weechat::info_get_hashtable("irc_message_parse", &input) {
+    ///     // Prints "#weechat: hello".
+    ///     Weechat::print(&format!("{}: {}", parsed["channel"], parsed["text"]));
+    /// }
+    /// ```
+    pub fn info_get_hashtable(
+        name: &str,
+        input: &HashMap<&str, &str>,
+    ) -> Option<HashMap<String, String>> {
         Weechat::check_thread();
         let weechat = unsafe { Weechat::weechat() };
 
-        let string_eval_expression = weechat.get().string_eval_expression.unwrap();
+        let info_get_hashtable = weechat.get().info_get_hashtable.unwrap();
+        let hashtable_free = weechat.get().hashtable_free.unwrap();
 
-        let expr = LossyCString::new(expression);
+        let info_name = LossyCString::new(name);
+        let input = weechat.hashmap_to_weechat(input.clone());
 
         unsafe {
-            let result = string_eval_expression(
-                expr.as_ptr(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-            );
+            let info = info_get_hashtable(weechat.ptr, info_name.as_ptr(), input);
+            hashtable_free(input);
 
-            if result.is_null() {
-                Err(())
+            if info.is_null() {
+                None
             } else {
-                Ok(CStr::from_ptr(result).to_string_lossy().to_string())
+                let info_map = weechat.hashtable_to_hashmap(info);
+                hashtable_free(info);
+                Some(info_map)
             }
         }
     }
 
-    /// Get the Weechat homedir.
-    pub fn home_dir() -> PathBuf {
+    /// Remove WeeChat colors from a string.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string that should be stripped from Weechat colors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn remove_color(string: &str) -> String {
         Weechat::check_thread();
         let weechat = unsafe { Weechat::weechat() };
 
-        let eval_path_home = weechat.get().string_eval_path_home.unwrap();
-
-        let path = LossyCString::new("%h");
-
-        let path = unsafe {
-            let result = eval_path_home(
-                path.as_ptr(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-            );
+        let string = LossyCString::new(string);
 
-            if result.is_null() {
-                panic!("Returned null while evaluating the Weechat home dir");
-            } else {
-                CStr::from_ptr(result).to_string_lossy().to_string()
-            }
-        };
+        let remove_color = weechat.get().string_remove_color.unwrap();
 
-        PathBuf::from(path)
+        unsafe {
+            let ptr = remove_color(string.as_ptr(), ptr::null());
+            Weechat::take_string(ptr)
+        }
     }
 
-    /// Replace a leading `~` with the home directory.
+    /// Get the size in bytes of the Weechat color code at the given offset.
     ///
-    /// If the string does not start with `~`, the same string is returned.
-    pub fn expand_home(string: &str) -> String {
-        Weechat::check_thread();
+    /// This can be used to skip over color codes while walking over a string
+    /// that contains Weechat colors. The plugin API version this crate binds
+    /// doesn't provide `string_color_code_size()`, the color code format is
+    /// parsed the same way Weechat does it.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string containing Weechat color codes.
+    ///
+    /// * `offset` - The byte offset where the color code starts.
+    ///
+    /// Returns 0 if there is no color code at the given offset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use weechat::Weechat;
+    /// // The foreground color 05 followed by some text.
+    /// let string = "\x19F05text";
+    ///
+    /// assert_eq!(Weechat::color_code_size(string, 0), 4);
+    /// assert_eq!(Weechat::color_code_size(string, 4), 0);
+    /// ```
+    pub fn color_code_size(string: &str, offset: usize) -> usize {
+        let bytes = string.as_bytes().get(offset..).unwrap_or_default();
+        let at = |i: usize| bytes.get(i).copied().unwrap_or(0);
+        let is_attr = |c: u8| matches!(c, b'*' | b'!' | b'/' | b'_' | b'|');
+
+        // Skip a color number that may be prefixed by attributes, extended
+        // colors are marked with a @ and have 5 digits, other colors have 2.
+        let color_number = |mut i: usize, allow_attrs: bool| {
+            let extended = at(i) == b'@';
+
+            if extended {
+                i += 1;
+            }
 
-        let weechat = unsafe { Weechat::weechat() };
-        let expand = weechat.get().string_expand_home.unwrap();
-        let string = LossyCString::new(string);
+            while allow_attrs && is_attr(at(i)) {
+                i += 1;
+            }
 
-        let string = unsafe {
-            let result = expand(string.as_ptr());
+            let digits = if extended { 5 } else { 2 };
 
-            if result.is_null() {
-                panic!("Returned null while expanding the home dir");
+            if bytes.len() >= i + digits {
+                i + digits
             } else {
-                CStr::from_ptr(result).to_string_lossy().to_string()
+                i
             }
         };
 
-        string
+        match at(0) {
+            COLOR => match at(1) {
+                b'F' => color_number(2, true),
+                b'B' => color_number(2, false),
+                b'*' => {
+                    let mut i = color_number(2, true);
+
+                    if matches!(at(i), b',' | b'~') {
+                        i += 1;
+                    }
+
+                    color_number(i, false)
+                }
+                b'@' => color_number(1, false),
+                b'E' | RESET => 2,
+                b'b' => match at(2) {
+                    b'F' | b'D' | b'B' | b'_' | b'-' | b'#' | b'i' | b'l' => 3,
+                    _ => 2,
+                },
+                c if c.is_ascii_digit() && at(2).is_ascii_digit() => 3,
+                _ => 1,
+            },
+            SET_ATTR | REMOVE_ATTR => {
+                if is_attr(at(1)) {
+                    2
+                } else {
+                    1
+                }
+            }
+            RESET => 1,
+            _ => 0,
+        }
     }
 
-    /// Execute a modifier.
+    /// Remove only some kinds of Weechat color codes from a string.
     ///
-    /// A modifier takes a string and modifies it in some way, Weechat has a
-    /// list of defined modifiers. For example to parse a string with some color
-    /// format (ansi, irc...) and to convert it to another format.
-    ///
-    /// Returns the modified string or an empty error if the string couldn't be
-    /// modified.
+    /// Weechat's `string_remove_color()` always removes every color code,
+    /// this walks the string using `color_code_size()` instead and keeps the
+    /// color codes whose kind isn't listed, e.g. to drop the colors of a
+    /// message but keep it bold.
     ///
     /// # Arguments
     ///
-    /// * `modifier` - The name of a modifier. The list of modifiers can be found in
-    ///     the official
-    /// [Weechat documentation](https://weechat.org/files/doc/stable/weechat_plugin_api.en.html#_hook_modifier_exec).
+    /// * `string` - The string containing Weechat color codes.
     ///
-    /// * `modifier_data` - Data that will be passed to the modifier, this
-    /// depends on the modifier that was chosen, consult the list of modifiers
-    /// in the Weechat documentation.
+    /// * `kinds` - The kinds of color codes that should be removed.
     ///
-    /// * `input_string` - The string that should be modified.
+    /// # Example
     ///
-    /// # Panics
+    /// ```
+    /// # use weechat::{ColorCodeKind, Weechat};
+    /// // Bold, the foreground color 05, then some text.
+    /// let message = Weechat::remove_color_chars("\x1A*\x19F05hello", &[ColorCodeKind::Color]);
     ///
-    /// Panics if the method is not called from the main Weechat thread.
-    pub fn execute_modifier(
-        modifier: &str,
-        modifier_data: &str,
-        input_string: &str,
-    ) -> Result<String, ()> {
-        Weechat::check_thread();
-        let weechat = unsafe { Weechat::weechat() };
-
-        let exec = weechat.get().hook_modifier_exec.unwrap();
-
-        let modifier = LossyCString::new(modifier);
-        let modifier_data = LossyCString::new(modifier_data);
-        let input_string = LossyCString::new(input_string);
+    /// assert_eq!(message, "\x1A*hello");
+    /// ```
+    pub fn remove_color_chars(string: &str, kinds: &[ColorCodeKind]) -> String {
+        let bytes = string.as_bytes();
+        let mut stripped = Vec::with_capacity(bytes.len());
+        let mut offset = 0;
+
+        while offset < bytes.len() {
+            let size = Weechat::color_code_size(string, offset);
+
+            if size == 0 {
+                stripped.push(bytes[offset]);
+                offset += 1;
+                continue;
+            }
 
-        unsafe {
-            let result = exec(
-                weechat.ptr,
-                modifier.as_ptr(),
-                modifier_data.as_ptr(),
-                input_string.as_ptr(),
-            );
+            let remove = ColorCodeKind::from_byte(bytes[offset])
+                .map(|kind| kinds.contains(&kind))
+                .unwrap_or(false);
 
-            if result.is_null() {
-                Err(())
-            } else {
-                Ok(CStr::from_ptr(result).to_string_lossy().to_string())
+            if !remove {
+                stripped.extend_from_slice(&bytes[offset..offset + size]);
             }
+
+            offset += size;
         }
+
+        // A truncated color code can swallow a part of a multi-byte
+        // character.
+        String::from_utf8_lossy(&stripped).into_owned()
     }
 
-    /// Update the content of a bar item, by calling its build callback.
+    /// Replace WeeChat colors in a string with a character.
+    ///
+    /// Every color code is replaced with the given character, this keeps the
+    /// positions of the colors visible, e.g. for debugging.
     ///
     /// # Arguments
     ///
-    /// * `name` - The name of the bar item that should be updated.
-    pub fn bar_item_update(name: &str) {
+    /// * `string` - The string that should be stripped from Weechat colors.
+    ///
+    /// * `replacement` - The character that replaces every color code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn remove_color_with_replacement(string: &str, replacement: char) -> String {
         Weechat::check_thread();
         let weechat = unsafe { Weechat::weechat() };
 
-        let bar_item_update = weechat.get().bar_item_update.unwrap();
+        let string = LossyCString::new(string);
+        let replacement = LossyCString::new(replacement.to_string());
 
-        let name = LossyCString::new(name);
+        let remove_color = weechat.get().string_remove_color.unwrap();
 
-        unsafe { bar_item_update(name.as_ptr()) }
+        unsafe {
+            let ptr = remove_color(string.as_ptr(), replacement.as_ptr());
+            Weechat::take_string(ptr)
+        }
     }
 
-    /// Spawn a new `Future` on the main Weechat thread.
+    /// Get the number of columns that are needed to display a string
+    /// containing Weechat colors.
     ///
-    /// # Panics
+    /// Color codes don't take up any columns, wide characters take up two
+    /// columns like with `string_width()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string that should be measured.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// let item = format!("{}日本{}", Weechat::color("red"), Weechat::color("reset"));
+    ///
+    /// // The two wide characters take up 4 columns, the colors none.
+    /// let width = Weechat::length_on_screen(&item);
+    ///
+    /// // Truncate the item to fit into a bar item with 3 free columns.
+    /// let item = Weechat::string_cut(&Weechat::remove_color(&item), 3, "+");
+    /// ```
+    pub fn length_on_screen(string: &str) -> i32 {
+        Weechat::string_width(&Weechat::remove_color(string))
+    }
+
+    /// Check if a string matches a mask.
+    ///
+    /// The mask can contain the wildcard `*`, which matches any number of
+    /// characters, including none, e.g. `irc.*.#rust` matches
+    /// `irc.libera.#rust`.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string that should be matched.
+    ///
+    /// * `mask` - The mask the string should be matched against.
+    ///
+    /// * `case_sensitive` - Should the matching be case sensitive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn string_match(string: &str, mask: &str, case_sensitive: bool) -> bool {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let string_match = weechat.get().string_match.unwrap();
+
+        let string = LossyCString::new(string);
+        let mask = LossyCString::new(mask);
+
+        unsafe { string_match(string.as_ptr(), mask.as_ptr(), case_sensitive as c_int) == 1 }
+    }
+
+    /// Check if a string matches a list of masks.
+    ///
+    /// The masks can contain the wildcard `*`. A mask can be negated by
+    /// prefixing it with `!`, negated masks have a higher priority than
+    /// normal masks, e.g. the masks `irc.*` and `!irc.*.#rust` match every IRC
+    /// buffer except the `#rust` channels.
+    ///
+    /// Returns true if the string matches at least one mask and none of the
+    /// negated masks.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string that should be matched.
+    ///
+    /// * `masks` - The masks the string should be matched against.
+    ///
+    /// * `case_sensitive` - Should the matching be case sensitive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// let masks = ["irc.*", "!irc.*.#rust"];
+    ///
+    /// // Matches, the buffer isn't a #rust channel.
+    /// let weechat_channel = Weechat::string_match_list("irc.libera.#weechat", &masks, false);
+    ///
+    /// // Doesn't match, the negated mask wins.
+    /// let rust_channel = Weechat::string_match_list("irc.libera.#rust", &masks, false);
+    /// ```
+    pub fn string_match_list(string: &str, masks: &[&str], case_sensitive: bool) -> bool {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let string_match_list = weechat.get().string_match_list.unwrap();
+
+        let string = LossyCString::new(string);
+        let masks: Vec<CString> = masks.iter().map(LossyCString::new).collect();
+        let mut mask_ptrs: Vec<*const c_char> = masks.iter().map(|m| m.as_ptr()).collect();
+        mask_ptrs.push(ptr::null());
+
+        unsafe {
+            string_match_list(
+                string.as_ptr(),
+                mask_ptrs.as_mut_ptr(),
+                case_sensitive as c_int,
+            ) == 1
+        }
+    }
+
+    /// Convert ANSI color codes in a string to Weechat color codes.
+    ///
+    /// This handles SGR sequences, e.g. `\x1b[1;31m` for bold red text, ANSI
+    /// sequences that aren't supported are removed from the string.
+    ///
+    /// The string is returned unchanged if it couldn't be decoded.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string containing ANSI color codes, e.g. the output of
+    ///     an external program.
+    ///
+    /// * `keep_colors` - Should the colors be converted to Weechat colors, if
+    ///     false all the ANSI color codes are removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// let line = Weechat::decode_ansi("\x1b[1;31merror\x1b[0m: file not found", true);
+    /// Weechat::print(&line);
+    /// ```
+    pub fn decode_ansi(string: &str, keep_colors: bool) -> String {
+        let keep_colors = if keep_colors { "1" } else { "0" };
+
+        Weechat::execute_modifier("color_decode_ansi", keep_colors, string)
+            .unwrap_or_else(|_| string.to_owned())
+    }
+
+    /// Get the number of UTF-8 characters in a string.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string that should be measured.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn string_length(string: &str) -> i32 {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let utf8_strlen = weechat.get().utf8_strlen.unwrap();
+        let string = LossyCString::new(string);
+
+        unsafe { utf8_strlen(string.as_ptr()) }
+    }
+
+    /// Get the number of columns that are needed to display a string.
+    ///
+    /// Wide characters, e.g. CJK characters, take up two columns while
+    /// combining characters don't take up any.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string that should be measured.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn string_width(string: &str) -> i32 {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let utf8_strlen_screen = weechat.get().utf8_strlen_screen.unwrap();
+        let string = LossyCString::new(string);
+
+        unsafe { utf8_strlen_screen(string.as_ptr()) }
+    }
+
+    /// Cut a string so it can be displayed in the given number of columns.
+    ///
+    /// If the string needs to be cut, the suffix is appended to the cut
+    /// string, the suffix is included in the width. If the suffix itself
+    /// doesn't fit into the width, only the part of the suffix that fits is
+    /// returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string that should be cut.
+    ///
+    /// * `width` - The maximum number of columns the string may take up.
+    ///
+    /// * `suffix` - A suffix that is appended if the string was cut, e.g.
+    ///     `…`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// // "weec+", the suffix is counted in the width.
+    /// let cut = Weechat::string_cut("weechat", 5, "+");
+    ///
+    /// // "日本", the characters take up two columns each.
+    /// let cut = Weechat::string_cut("日本語", 4, "");
+    ///
+    /// // "..", a suffix wider than the width is cut as well.
+    /// let cut = Weechat::string_cut("weechat", 2, "...");
+    /// ```
+    pub fn string_cut(string: &str, width: i32, suffix: &str) -> String {
+        if Weechat::string_width(string) <= width {
+            return string.to_owned();
+        }
+
+        let weechat = unsafe { Weechat::weechat() };
+        let utf8_char_size_screen = weechat.get().utf8_char_size_screen.unwrap();
+
+        let char_width = |c: &str| {
+            let c = LossyCString::new(c);
+            unsafe { utf8_char_size_screen(c.as_ptr()) }.max(0)
+        };
+
+        let suffix_width = Weechat::string_width(suffix);
+
+        if suffix_width >= width {
+            return cut_to_width(suffix, width, char_width);
+        }
+
+        let mut result = cut_to_width(string, width - suffix_width, char_width);
+        result.push_str(suffix);
+        result
+    }
+
+    /// Convert a string array that was split by Weechat into a vector of
+    /// strings and free the array.
+    unsafe fn split_to_vec(&self, split: *mut *mut c_char, num_items: c_int) -> Vec<String> {
+        if split.is_null() {
+            return Vec::new();
+        }
+
+        let items = (0..num_items as isize)
+            .map(|i| {
+                CStr::from_ptr(*split.offset(i))
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+
+        let string_free_split = self.get().string_free_split.unwrap();
+        string_free_split(split);
+
+        items
+    }
+
+    /// Split a string the same way Weechat splits it.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string that should be split.
+    ///
+    /// * `separators` - The characters that separate the items, every
+    ///     character is a separator on its own.
+    ///
+    /// * `flags` - Flags that control how the string is split.
+    ///
+    /// * `max_items` - The maximum number of items that should be returned, 0
+    ///     means no limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::{SplitFlags, Weechat};
+    /// // ["a", "b"], empty items are removed.
+    /// let split = Weechat::string_split(",a,,b,", ",", SplitFlags::strip_and_collapse(), 0);
+    ///
+    /// // ["a", "", "b"], the empty item between the separators is kept.
+    /// let split = Weechat::string_split("a,,b", ",", SplitFlags::default(), 0);
+    /// ```
+    pub fn string_split(
+        string: &str,
+        separators: &str,
+        flags: SplitFlags,
+        max_items: i32,
+    ) -> Vec<String> {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let string_split = weechat.get().string_split.unwrap();
+        let string = LossyCString::new(string);
+        let separators = LossyCString::new(separators);
+        let mut num_items = 0;
+
+        unsafe {
+            let split = string_split(
+                string.as_ptr(),
+                separators.as_ptr(),
+                ptr::null(),
+                flags.bits(),
+                max_items,
+                &mut num_items,
+            );
+            weechat.split_to_vec(split, num_items)
+        }
+    }
+
+    /// Split a string like a shell would do it.
+    ///
+    /// Arguments can be quoted using single or double quotes and spaces can be
+    /// escaped using a backslash.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string that should be split.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// // ["grep", "hello world", "it's"]
+    /// let split = Weechat::string_split_shell(r#"grep "hello world" it\'s"#);
+    /// ```
+    pub fn string_split_shell(string: &str) -> Vec<String> {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let string_split_shell = weechat.get().string_split_shell.unwrap();
+        let string = LossyCString::new(string);
+        let mut num_items = 0;
+
+        unsafe {
+            let split = string_split_shell(string.as_ptr(), &mut num_items);
+            weechat.split_to_vec(split, num_items)
+        }
+    }
+
+    /// Get the text that would be sent to a buffer for the given input.
+    ///
+    /// Returns `None` if the input is a command, otherwise the text part of
+    /// the input is returned. A leading double command char is treated as an
+    /// escaped command char, e.g. `//help` results in `/help`.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input that should be checked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// // None, this is a command.
+    /// let input = Weechat::string_input_for_buffer("/help");
+    ///
+    /// // Some("/help"), the command char was escaped.
+    /// let input = Weechat::string_input_for_buffer("//help");
+    ///
+    /// // Some("hello")
+    /// let input = Weechat::string_input_for_buffer("hello");
+    /// ```
+    pub fn string_input_for_buffer(input: &str) -> Option<String> {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let string_input_for_buffer = weechat.get().string_input_for_buffer.unwrap();
+        let input = LossyCString::new(input);
+
+        unsafe {
+            let text = string_input_for_buffer(input.as_ptr());
+
+            if text.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(text).to_string_lossy().to_string())
+            }
+        }
+    }
+
+    /// Evaluate a Weechat expression and return the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - The expression that should be evaluated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// let nick_color = Weechat::eval_string_expression("${color:cyan}nick");
+    /// ```
+    pub fn eval_string_expression(expression: &str) -> Result<String, ()> {
+        Weechat::eval_string_expression_with(expression, &HashMap::new(), &HashMap::new())
+    }
+
+    /// Evaluate a Weechat expression with custom variables and options and
+    /// return the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - The expression that should be evaluated.
+    ///
+    /// * `variables` - Variables that will be replaced in the expression, a
+    ///     variable with the name `nick` replaces `${nick}`.
+    ///
+    /// * `options` - Options for the evaluation, e.g. `type` set to
+    ///     `condition` to evaluate a condition or `extra` set to `eval` to
+    ///     evaluate the variables as well. The full list of options can be
+    ///     found in the [Weechat documentation].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use weechat::Weechat;
+    /// let mut variables = HashMap::new();
+    /// variables.insert("nick", "alice");
+    /// variables.insert("channel", "#weechat");
+    ///
+    /// let message = Weechat::eval_string_expression_with(
+    ///     "${nick} joined ${channel}",
+    ///     &variables,
+    ///     &HashMap::new(),
+    /// );
+    /// ```
+    ///
+    /// [Weechat documentation]: https://weechat.org/files/doc/stable/weechat_plugin_api.en.html#_string_eval_expression
+    pub fn eval_string_expression_with(
+        expression: &str,
+        variables: &HashMap<&str, &str>,
+        options: &HashMap<&str, &str>,
+    ) -> Result<String, ()> {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let string_eval_expression = weechat.get().string_eval_expression.unwrap();
+        let hashtable_free = weechat.get().hashtable_free.unwrap();
+
+        let to_hashtable = |hashmap: &HashMap<&str, &str>| {
+            if hashmap.is_empty() {
+                ptr::null_mut()
+            } else {
+                weechat.hashmap_to_weechat(hashmap.clone())
+            }
+        };
+
+        let expr = LossyCString::new(expression);
+        let variables = to_hashtable(variables);
+        let options = to_hashtable(options);
+
+        unsafe {
+            let result = string_eval_expression(expr.as_ptr(), ptr::null_mut(), variables, options);
+
+            if !variables.is_null() {
+                hashtable_free(variables);
+            }
+
+            if !options.is_null() {
+                hashtable_free(options);
+            }
+
+            if result.is_null() {
+                Err(())
+            } else {
+                Ok(CStr::from_ptr(result).to_string_lossy().to_string())
+            }
+        }
+    }
+
+    /// Get the Weechat homedir.
+    pub fn home_dir() -> PathBuf {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let eval_path_home = weechat.get().string_eval_path_home.unwrap();
+
+        let path = LossyCString::new("%h");
+
+        let path = unsafe {
+            let result = eval_path_home(
+                path.as_ptr(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            );
+
+            if result.is_null() {
+                panic!("Returned null while evaluating the Weechat home dir");
+            } else {
+                CStr::from_ptr(result).to_string_lossy().to_string()
+            }
+        };
+
+        PathBuf::from(path)
+    }
+
+    /// Get the version of the running Weechat, e.g. `3.8`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn version() -> String {
+        Weechat::info_get("version", "").unwrap_or_default()
+    }
+
+    /// Get the version of the running Weechat as a number.
+    ///
+    /// Every part of the version is encoded as a byte, from the most
+    /// significant byte to the least significant one: major, minor, patch and
+    /// a last byte that is 0 for releases. For example version 3.8 is
+    /// represented as `0x03080000`, this makes versions comparable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// if Weechat::version_number() >= 0x03020000 {
+    ///     Weechat::print("Weechat uses separate config and data dirs");
+    /// }
+    /// ```
+    pub fn version_number() -> u32 {
+        Weechat::info_get("version_number", "")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default()
+    }
+
+    fn dir_from_info(info_name: &str) -> PathBuf {
+        match Weechat::info_get(info_name, "") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            // Weechat versions before 3.2 use a single directory for
+            // everything.
+            _ => Weechat::home_dir(),
+        }
+    }
+
+    /// Get the Weechat config dir.
+    ///
+    /// Falls back to the Weechat homedir on Weechat versions that don't split
+    /// their directories.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn config_dir() -> PathBuf {
+        Weechat::dir_from_info("weechat_config_dir")
+    }
+
+    /// Get the Weechat data dir.
+    ///
+    /// Falls back to the Weechat homedir on Weechat versions that don't split
+    /// their directories.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn data_dir() -> PathBuf {
+        Weechat::dir_from_info("weechat_data_dir")
+    }
+
+    /// Get the Weechat cache dir.
+    ///
+    /// Falls back to the Weechat homedir on Weechat versions that don't split
+    /// their directories.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn cache_dir() -> PathBuf {
+        Weechat::dir_from_info("weechat_cache_dir")
+    }
+
+    /// Get the Weechat runtime dir.
+    ///
+    /// Falls back to the Weechat homedir on Weechat versions that don't split
+    /// their directories.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn runtime_dir() -> PathBuf {
+        Weechat::dir_from_info("weechat_runtime_dir")
+    }
+
+    /// Create a directory in the Weechat home.
+    ///
+    /// The directory is created in the Weechat data directory, on Weechat
+    /// versions that don't split their directories this is the Weechat
+    /// homedir. Only the last component of the path is created, use
+    /// `mkdir_parents_home()` to create missing parent directories as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the directory, relative to the Weechat data
+    ///     directory.
+    ///
+    /// * `mode` - The permission mode of the new directory, e.g. `0o755`,
+    ///     the process umask is applied to it.
+    ///
+    /// Returns an empty error if the directory couldn't be created, an
+    /// already existing directory is not an error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// Weechat::mkdir_home("my_plugin", 0o755).expect("Can't create the plugin directory");
+    /// ```
+    pub fn mkdir_home(path: &str, mode: u32) -> Result<(), ()> {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let mkdir_home = weechat.get().mkdir_home.unwrap();
+        let path = LossyCString::new(path);
+
+        if unsafe { mkdir_home(path.as_ptr(), mode as c_int) } != 0 {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Create a directory and all of its missing parents in the Weechat home.
+    ///
+    /// The directory is created in the same base directory as the one
+    /// `mkdir_home()` uses.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the directory, relative to the Weechat data
+    ///     directory.
+    ///
+    /// * `mode` - The permission mode of the new directories, e.g. `0o755`,
+    ///     the process umask is applied to it.
+    ///
+    /// Returns an empty error if one of the directories couldn't be created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn mkdir_parents_home(path: &str, mode: u32) -> Result<(), ()> {
+        let path = Weechat::data_dir().join(path);
+
+        let weechat = unsafe { Weechat::weechat() };
+        let mkdir_parents = weechat.get().mkdir_parents.unwrap();
+        let path = LossyCString::new(path.to_string_lossy());
+
+        if unsafe { mkdir_parents(path.as_ptr(), mode as c_int) } != 0 {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Replace a leading `~` with the home directory.
+    ///
+    /// If the string does not start with `~`, the same string is returned.
+    pub fn expand_home(string: &str) -> String {
+        Weechat::check_thread();
+
+        let weechat = unsafe { Weechat::weechat() };
+        let expand = weechat.get().string_expand_home.unwrap();
+        let string = LossyCString::new(string);
+
+        let string = unsafe {
+            let result = expand(string.as_ptr());
+
+            if result.is_null() {
+                panic!("Returned null while expanding the home dir");
+            } else {
+                CStr::from_ptr(result).to_string_lossy().to_string()
+            }
+        };
+
+        string
+    }
+
+    /// Execute a modifier.
+    ///
+    /// A modifier takes a string and modifies it in some way, Weechat has a
+    /// list of defined modifiers. For example to parse a string with some color
+    /// format (ansi, irc...) and to convert it to another format.
+    ///
+    /// Returns the modified string or an empty error if the string couldn't be
+    /// modified.
+    ///
+    /// # Arguments
+    ///
+    /// * `modifier` - The name of a modifier. The list of modifiers can be found in
+    ///     the official
+    /// [Weechat documentation](https://weechat.org/files/doc/stable/weechat_plugin_api.en.html#_hook_modifier_exec).
+    ///
+    /// * `modifier_data` - Data that will be passed to the modifier, this
+    /// depends on the modifier that was chosen, consult the list of modifiers
+    /// in the Weechat documentation.
+    ///
+    /// * `input_string` - The string that should be modified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn execute_modifier(
+        modifier: &str,
+        modifier_data: &str,
+        input_string: &str,
+    ) -> Result<String, ()> {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let exec = weechat.get().hook_modifier_exec.unwrap();
+
+        let modifier = LossyCString::new(modifier);
+        let modifier_data = LossyCString::new(modifier_data);
+        let input_string = LossyCString::new(input_string);
+
+        unsafe {
+            let result = exec(
+                weechat.ptr,
+                modifier.as_ptr(),
+                modifier_data.as_ptr(),
+                input_string.as_ptr(),
+            );
+
+            if result.is_null() {
+                Err(())
+            } else {
+                Ok(CStr::from_ptr(result).to_string_lossy().to_string())
+            }
+        }
+    }
+
+    /// Convert IRC color codes in a string to Weechat colors.
+    ///
+    /// This executes the `irc_color_decode` modifier of the IRC plugin, if
+    /// the IRC plugin isn't loaded the string is returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string containing IRC color codes, e.g. a raw IRC
+    ///     message.
+    ///
+    /// * `keep_colors` - If true, the IRC colors are converted to the
+    ///     corresponding Weechat colors, otherwise the IRC colors are removed
+    ///     from the string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn irc_color_decode(string: &str, keep_colors: bool) -> String {
+        Weechat::execute_modifier(
+            "irc_color_decode",
+            if keep_colors { "1" } else { "0" },
+            string,
+        )
+        .unwrap_or_else(|_| string.to_owned())
+    }
+
+    /// Convert the Weechat colors of a string to IRC color codes.
+    ///
+    /// This executes the `irc_color_encode` modifier of the IRC plugin, if
+    /// the IRC plugin isn't loaded the string is returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string containing Weechat colors, e.g. a message that
+    ///     should be sent to an IRC server.
+    ///
+    /// * `keep_colors` - If true, the Weechat colors are converted to the
+    ///     corresponding IRC color codes, otherwise the colors are removed
+    ///     from the string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn irc_color_encode(string: &str, keep_colors: bool) -> String {
+        Weechat::execute_modifier(
+            "irc_color_encode",
+            if keep_colors { "1" } else { "0" },
+            string,
+        )
+        .unwrap_or_else(|_| string.to_owned())
+    }
+
+    /// Update the content of a bar item, by calling its build callback.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the bar item that should be updated.
+    pub fn bar_item_update(name: &str) {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let bar_item_update = weechat.get().bar_item_update.unwrap();
+
+        let name = LossyCString::new(name);
+
+        unsafe { bar_item_update(name.as_ptr()) }
+    }
+
+    /// Update the content of multiple bar items, by calling their build
+    /// callbacks.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - The names of the bar items that should be updated.
+    pub fn bar_item_update_all(names: &[&str]) {
+        for name in names {
+            Weechat::bar_item_update(name);
+        }
+    }
+
+    /// Spawn a new `Future` on the main Weechat thread.
+    ///
+    /// # Cancellation
+    ///
+    /// The future is cancelled if the returned `Task` is dropped, use
+    /// `Task::detach()` to let the future run in the background. A task can
+    /// be cancelled explicitly using `Task::cancel()`, once a task is
+    /// cancelled its future is dropped and it will never be polled again,
+    /// even if it was already woken up.
+    ///
+    /// Tasks should be cancelled in the `Drop` implementation of the plugin
+    /// so they don't access plugin state that is already gone. Once the
+    /// plugin is dropped, the executor is stopped and all the futures that
+    /// are waiting to be polled are dropped, detached tasks that are still
+    /// waiting for an event are dropped the next time they are woken up.
+    ///
+    /// # Panics
     ///
     /// Panics if the method is not called from the main Weechat thread or if
     /// the method is called in a buffer close callback when Weechat is shutting
@@ -634,8 +1835,11 @@ impl Weechat {
     ///
     /// let (tx, rx) = channel(1000);
     ///
-    /// Weechat::spawn(task(rx));
+    /// let task = Weechat::spawn(task(rx));
     /// block_on(tx.send("Hello world".to_string()));
+    ///
+    /// // Stop the task, it won't be polled anymore.
+    /// drop(task);
     /// ```
     #[cfg(feature = "async")]
     #[cfg_attr(feature = "docs", doc(cfg(r#async)))]
@@ -717,3 +1921,334 @@ impl Weechat {
         WeechatExecutor::spawn_buffer_cb(buffer_name, future)
     }
 }
+
+/// Take characters from the start of the string as long as they fit into the
+/// given width.
+fn cut_to_width(string: &str, width: i32, char_width: impl Fn(&str) -> i32) -> String {
+    let mut used = 0;
+    let mut result = String::new();
+    let mut buffer = [0; 4];
+
+    for c in string.chars() {
+        let c: &str = c.encode_utf8(&mut buffer);
+        let width_of_char = char_width(c);
+
+        if used + width_of_char > width {
+            break;
+        }
+
+        used += width_of_char;
+        result.push_str(c);
+    }
+
+    result
+}
+
+/// Color the characters at the given indices, grouping consecutive characters
+/// that share a color.
+fn colorize_spans(
+    text: &str,
+    indices: &[usize],
+    highlight_color: &str,
+    normal_color: &str,
+    reset: &str,
+) -> String {
+    let mut indices = indices.to_vec();
+    indices.sort_unstable();
+
+    let mut colored = String::with_capacity(text.len());
+    let mut current_color = None;
+
+    for (i, character) in text.chars().enumerate() {
+        let color = if indices.binary_search(&i).is_ok() {
+            highlight_color
+        } else {
+            normal_color
+        };
+
+        if current_color != Some(color) {
+            if current_color.is_some() {
+                colored.push_str(reset);
+            }
+
+            colored.push_str(color);
+            current_color = Some(color);
+        }
+
+        colored.push(character);
+    }
+
+    if current_color.is_some() {
+        colored.push_str(reset);
+    }
+
+    colored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_color_chars_keeps_other_kinds() {
+        let string = "\x19F05\x1A*bold\x1B*\x1Cdone";
+
+        assert_eq!(
+            Weechat::remove_color_chars(string, &[ColorCodeKind::Color]),
+            "\x1A*bold\x1B*\x1Cdone"
+        );
+        assert_eq!(
+            Weechat::remove_color_chars(
+                string,
+                &[ColorCodeKind::SetAttribute, ColorCodeKind::RemoveAttribute]
+            ),
+            "\x19F05bold\x1Cdone"
+        );
+        assert_eq!(
+            Weechat::remove_color_chars(string, &[ColorCodeKind::Reset]),
+            "\x19F05\x1A*bold\x1B*done"
+        );
+    }
+
+    #[test]
+    fn remove_color_chars_removes_every_kind() {
+        let kinds = [
+            ColorCodeKind::Color,
+            ColorCodeKind::SetAttribute,
+            ColorCodeKind::RemoveAttribute,
+            ColorCodeKind::Reset,
+        ];
+
+        assert_eq!(
+            Weechat::remove_color_chars("\x19*05,03日本\x1A_語\x1C", &kinds),
+            "日本語"
+        );
+        assert_eq!(Weechat::remove_color_chars("plain", &kinds), "plain");
+        assert_eq!(
+            Weechat::remove_color_chars("plain\x19F05", &[]),
+            "plain\x19F05"
+        );
+    }
+
+    #[test]
+    fn colorize_spans_groups_consecutive_indices() {
+        let colored = colorize_spans("weechat", &[0, 1, 4], "<h>", "<n>", "<r>");
+
+        assert_eq!(colored, "<h>we<r><n>ec<r><h>h<r><n>at<r>");
+    }
+
+    #[test]
+    fn colorize_spans_accepts_unsorted_indices() {
+        let colored = colorize_spans("abc", &[2, 0], "<h>", "<n>", "<r>");
+
+        assert_eq!(colored, "<h>a<r><n>b<r><h>c<r>");
+    }
+
+    #[test]
+    fn colorize_spans_uses_char_indices() {
+        let colored = colorize_spans("äöü", &[1], "<h>", "<n>", "<r>");
+
+        assert_eq!(colored, "<n>ä<r><h>ö<r><n>ü<r>");
+    }
+
+    #[test]
+    fn colorize_spans_ignores_out_of_range_indices() {
+        assert_eq!(colorize_spans("ab", &[5], "<h>", "<n>", "<r>"), "<n>ab<r>");
+        assert_eq!(colorize_spans("", &[0], "<h>", "<n>", "<r>"), "");
+    }
+
+    /// The per character coloring the go example used before
+    /// `colorize_indices()` existed.
+    fn colorize_naive(
+        text: &str,
+        indices: &[usize],
+        highlight_color: &str,
+        normal_color: &str,
+        reset: &str,
+    ) -> String {
+        text.chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let color = if indices.contains(&i) {
+                    highlight_color
+                } else {
+                    normal_color
+                };
+
+                format!("{}{}{}", color, c, reset)
+            })
+            .collect()
+    }
+
+    // Run with `cargo test --release -- --ignored --nocapture colorize_bench`.
+    #[test]
+    #[ignore]
+    fn colorize_bench() {
+        use std::{hint::black_box, time::Instant};
+
+        const ROUNDS: u32 = 10_000;
+
+        let text = "irc.freenode.#weechat-rust-plugins".repeat(4);
+        let indices: Vec<usize> = (0..text.chars().count()).step_by(7).collect();
+        let (highlight, normal, reset) = ("\x19F*05", "\x19F03", "\x1C");
+
+        let naive = colorize_naive(&text, &indices, highlight, normal, reset);
+        let spans = colorize_spans(&text, &indices, highlight, normal, reset);
+        assert!(spans.len() < naive.len());
+
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            black_box(colorize_naive(
+                black_box(&text),
+                black_box(&indices),
+                highlight,
+                normal,
+                reset,
+            ));
+        }
+        let naive_time = start.elapsed();
+
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            black_box(colorize_spans(
+                black_box(&text),
+                black_box(&indices),
+                highlight,
+                normal,
+                reset,
+            ));
+        }
+        let spans_time = start.elapsed();
+
+        println!(
+            "naive: {:?} ({} bytes), colorize_spans: {:?} ({} bytes)",
+            naive_time / ROUNDS,
+            naive.len(),
+            spans_time / ROUNDS,
+            spans.len()
+        );
+    }
+
+    fn command_args(line: &str) -> Args {
+        let argv: Vec<String> = line.split(' ').map(|a| a.to_owned()).collect();
+        let lines = (0..argv.len()).map(|i| argv[i..].join(" ")).collect();
+
+        Args {
+            iter: argv.into_iter(),
+            lines,
+        }
+    }
+
+    #[test]
+    fn remaining_line_keeps_the_whole_line() {
+        let args = command_args(r#"/open "My Documents" it\'s\ me"#);
+
+        assert_eq!(args.remaining_line(), r#"/open "My Documents" it\'s\ me"#);
+    }
+
+    #[test]
+    fn remaining_line_skips_consumed_arguments() {
+        let mut args = command_args(r#"/open 'a "nested" quote' x\ y"#);
+        assert_eq!(args.next().as_deref(), Some("/open"));
+
+        assert_eq!(args.remaining_line(), r#"'a "nested" quote' x\ y"#);
+    }
+
+    #[test]
+    fn remaining_line_with_everything_consumed() {
+        let mut args = command_args("/open file");
+        args.next();
+        args.next();
+
+        assert_eq!(args.remaining_line(), "");
+    }
+
+    #[test]
+    fn remaining_line_without_argv_eol_joins_arguments() {
+        let mut args = Args {
+            iter: vec!["a".to_owned(), "b c".to_owned(), "d".to_owned()].into_iter(),
+            lines: Vec::new(),
+        };
+        args.next();
+
+        assert_eq!(args.remaining_line(), "b c d");
+    }
+
+    fn test_width(c: &str) -> i32 {
+        if c.is_ascii() {
+            1
+        } else if c.chars().all(|c| ('\u{300}'..='\u{36F}').contains(&c)) {
+            // Combining diacritical marks don't take up any space.
+            0
+        } else {
+            2
+        }
+    }
+
+    #[test]
+    fn cut_to_width_cuts_at_the_width() {
+        assert_eq!(cut_to_width("weechat", 4, test_width), "weec");
+        assert_eq!(cut_to_width("weechat", 10, test_width), "weechat");
+        assert_eq!(cut_to_width("weechat", 0, test_width), "");
+    }
+
+    #[test]
+    fn cut_to_width_doesnt_split_wide_chars() {
+        assert_eq!(cut_to_width("日本語", 4, test_width), "日本");
+        assert_eq!(cut_to_width("日本語", 3, test_width), "日");
+    }
+
+    #[test]
+    fn cut_to_width_cuts_a_wide_suffix() {
+        assert_eq!(cut_to_width("...", 2, test_width), "..");
+        assert_eq!(cut_to_width("…", 1, test_width), "");
+    }
+
+    #[test]
+    fn cut_to_width_keeps_combining_chars() {
+        assert_eq!(cut_to_width("e\u{301}x", 1, test_width), "e\u{301}");
+        assert_eq!(cut_to_width("e\u{301}x", 2, test_width), "e\u{301}x");
+        assert_eq!(cut_to_width("xe\u{301}", 1, test_width), "x");
+    }
+
+    #[test]
+    fn color_code_size_foreground() {
+        assert_eq!(Weechat::color_code_size("\x19F*05text", 0), 5);
+        assert_eq!(Weechat::color_code_size("\x19F@00123text", 0), 8);
+        assert_eq!(Weechat::color_code_size("\x19B05text", 0), 4);
+    }
+
+    #[test]
+    fn color_code_size_foreground_and_background() {
+        assert_eq!(Weechat::color_code_size("\x19*05~03text", 0), 7);
+        assert_eq!(Weechat::color_code_size("\x19*05,03text", 0), 7);
+    }
+
+    #[test]
+    fn color_code_size_short_codes() {
+        assert_eq!(Weechat::color_code_size("\x1905text", 0), 3);
+        assert_eq!(Weechat::color_code_size("\x19bFtext", 0), 3);
+        assert_eq!(Weechat::color_code_size("\x19Etext", 0), 2);
+        assert_eq!(Weechat::color_code_size("\x1A*text", 0), 2);
+        assert_eq!(Weechat::color_code_size("\x1B_text", 0), 2);
+        assert_eq!(Weechat::color_code_size("\x1Ctext", 0), 1);
+    }
+
+    #[test]
+    fn color_code_size_truncated() {
+        assert_eq!(Weechat::color_code_size("\x19", 0), 1);
+        assert_eq!(Weechat::color_code_size("\x19F0", 0), 2);
+        assert_eq!(Weechat::color_code_size("\x19@001", 0), 2);
+        assert_eq!(Weechat::color_code_size("\x1A", 0), 1);
+    }
+
+    #[test]
+    fn color_code_size_offset() {
+        let string = "ab\x1Ccd";
+
+        assert_eq!(Weechat::color_code_size(string, 0), 0);
+        assert_eq!(Weechat::color_code_size(string, 2), 1);
+        assert_eq!(Weechat::color_code_size(string, 3), 0);
+        assert_eq!(Weechat::color_code_size(string, 100), 0);
+    }
+}